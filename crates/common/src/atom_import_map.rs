@@ -1,84 +1,192 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use swc_core::ecma::{ast::*, atoms::Atom};
 
-use crate::ATOM_IMPORTS;
+use crate::{
+    unwrap_expr, wrappers::collect_wrapper_candidates, AtomName, Config, DetectWrappers,
+    FactoryRegistry, Patterns, FAMILY_FACTORIES,
+};
 
+/// Tracks which identifiers in a module refer to jotai atom factories.
+///
+/// Imports are keyed on [`Id`] rather than on the bare symbol, so once the
+/// `resolver` pass has run, a local binding that shadows an imported factory
+/// (a parameter, a catch clause binding or a block scoped variable) is not
+/// mistaken for the import.
 #[derive(Debug)]
 pub struct AtomImportMap {
     atom_names: Vec<Atom>,
-    imports: HashSet<Atom>,
-    namespace_imports: HashSet<Atom>,
+    import_sources: Patterns,
+    registry: FactoryRegistry,
+    imports: HashSet<Id>,
+    /// Imported atom family factories, such as `atomFamily`.
+    families: HashSet<Id>,
+    /// Local bindings that may hold a namespace of atom factories, such as
+    /// `J` in `import * as J from "jotai"`, mapped to the module they were
+    /// imported from.
+    namespace_imports: HashMap<Id, Atom>,
+    detect_wrappers: DetectWrappers,
+    /// Module-level functions returning an atom, e.g.
+    /// `function persisted(init) { return atomWithStorage(key, init) }`.
+    wrappers: HashSet<Id>,
 }
 
 impl AtomImportMap {
-    pub fn new(atom_names: Vec<Atom>) -> Self {
+    pub fn new(config: &Config) -> Self {
+        let import_sources: Vec<&str> = config
+            .import_sources
+            .iter()
+            .map(String::as_str)
+            .chain(config.atom_factories.keys().map(String::as_str))
+            .chain(config.atom_names.iter().filter_map(|n| match n {
+                AtomName::Import { from, .. } => Some(from.as_str()),
+                AtomName::Name(..) => None,
+            }))
+            .collect();
+
         AtomImportMap {
-            atom_names,
+            atom_names: config
+                .atom_names
+                .iter()
+                .filter_map(|n| match n {
+                    AtomName::Name(name) => Some(name.clone()),
+                    AtomName::Import { .. } => None,
+                })
+                .collect(),
+            import_sources: Patterns::new(&import_sources),
+            registry: FactoryRegistry::new(config),
             imports: Default::default(),
+            families: Default::default(),
             namespace_imports: Default::default(),
+            detect_wrappers: config.detect_wrappers,
+            wrappers: Default::default(),
+        }
+    }
+
+    /// Collects the imports and atom wrapper functions of a module. Must be
+    /// called before the module body is transformed.
+    pub fn visit_module(&mut self, module: &Module) {
+        for item in &module.body {
+            if let ModuleItem::ModuleDecl(ModuleDecl::Import(import)) = item {
+                self.visit_import_decl(import);
+            }
+        }
+
+        if self.detect_wrappers == DetectWrappers::Off {
+            return;
+        }
+        let mut candidates: Vec<_> = collect_wrapper_candidates(module)
+            .into_iter()
+            .filter(|c| !c.exported || self.detect_wrappers == DetectWrappers::All)
+            .collect();
+        // Wrappers may call other wrappers, so repeat until nothing changes.
+        loop {
+            let found = candidates
+                .iter()
+                .position(|candidate| candidate.body.returns_atom(self));
+            match found {
+                Some(i) => {
+                    self.wrappers.insert(candidates.swap_remove(i).id);
+                }
+                None => break,
+            }
         }
     }
 
     pub fn visit_import_decl(&mut self, import: &ImportDecl) {
-        if !import.src.value.starts_with("jotai") {
+        let src = import.src.value.to_atom_lossy().into_owned();
+        if !self.import_sources.matches(&src) {
             return;
         }
 
+        let factories = self.registry.factories(&src);
         for s in &import.specifiers {
-            let local_ident = match s {
+            match s {
                 ImportSpecifier::Named(ImportNamedSpecifier {
-                    local,
-                    imported: Some(ModuleExportName::Ident(ident)),
-                    ..
+                    local, imported, ..
                 }) => {
-                    if ATOM_IMPORTS.contains(&&*ident.sym) {
-                        local.sym.clone()
-                    } else {
-                        continue;
+                    let imported = match imported {
+                        Some(ModuleExportName::Ident(ident)) => ident.sym.clone(),
+                        Some(ModuleExportName::Str(str)) => str.value.to_atom_lossy().into_owned(),
+                        _ => local.sym.clone(),
+                    };
+                    if factories.contains(&imported) {
+                        self.imports.insert(local.to_id());
+                        if FAMILY_FACTORIES.contains(&&*imported) {
+                            self.families.insert(local.to_id());
+                        }
+                    } else if !self.registry.is_known(&src) {
+                        // Could be a namespace re-exported by a barrel module,
+                        // e.g. `export * as utils from "jotai/utils"`. Other
+                        // exports of known packages, such as `useAtom`, aren't.
+                        self.namespace_imports.insert(local.to_id(), src.clone());
                     }
                 }
-                ImportSpecifier::Named(ImportNamedSpecifier { local, .. }) => {
-                    if ATOM_IMPORTS.contains(&&*local.sym) {
-                        local.sym.clone()
-                    } else {
-                        continue;
+                ImportSpecifier::Default(ImportDefaultSpecifier { local, .. }) => {
+                    // A default export matches either by the `default` name or
+                    // by the name it is imported under.
+                    if factories.contains(&"default".into()) || factories.contains(&local.sym) {
+                        self.imports.insert(local.to_id());
                     }
                 }
-                ImportSpecifier::Namespace(..) => {
-                    self.namespace_imports
-                        .insert(import.src.value.to_atom_lossy().into_owned());
-                    continue;
+                ImportSpecifier::Namespace(ImportStarAsSpecifier { local, .. }) => {
+                    self.namespace_imports.insert(local.to_id(), src.clone());
                 }
+                #[cfg(swc_ast_unknown)]
                 _ => continue,
-            };
-
-            self.imports.insert(local_ident);
+            }
         }
     }
 
     pub fn is_atom_import(&self, expr: &Expr) -> bool {
-        match expr {
+        // Handles: atom(0) as PrimitiveAtom<number>, (atom<number>)(0), ...
+        match unwrap_expr(expr) {
             // Handles default export expressions
             Expr::Call(CallExpr {
                 callee: Callee::Expr(e),
                 ..
             }) => self.is_atom_import(e),
             // Handles: const countAtom = atom(0);
-            Expr::Ident(i) => self.atom_names.contains(&i.sym) || self.imports.contains(&i.sym),
+            Expr::Ident(i) => {
+                self.atom_names.contains(&i.sym)
+                    || self.imports.contains(&i.to_id())
+                    || self.wrappers.contains(&i.to_id())
+            }
             // Handles: const countAtom = jotai.atom(0);
+            // and: const countAtom = state.utils.atomWithReset(0);
             Expr::Member(MemberExpr {
                 obj,
                 prop: MemberProp::Ident(prop),
                 ..
-            }) => {
-                if let Expr::Ident(obj) = &**obj {
-                    if self.namespace_imports.contains(&obj.sym) {
-                        return ATOM_IMPORTS.contains(&&*prop.sym);
-                    }
-                }
-                false
-            }
+            }) => self
+                .namespace_source(obj)
+                .is_some_and(|src| self.registry.factories(src).contains(&prop.sym)),
             _ => false,
         }
     }
+
+    /// Whether `expr` is an atom family factory, e.g. `atomFamily` or
+    /// `utils.atomFamily`.
+    pub fn is_family_import(&self, expr: &Expr) -> bool {
+        match unwrap_expr(expr) {
+            Expr::Ident(i) => self.families.contains(&i.to_id()),
+            Expr::Member(MemberExpr {
+                prop: MemberProp::Ident(prop),
+                ..
+            }) => FAMILY_FACTORIES.contains(&&*prop.sym) && self.is_atom_import(expr),
+            _ => false,
+        }
+    }
+
+    /// Returns the module a namespace expression was imported from.
+    fn namespace_source(&self, expr: &Expr) -> Option<&Atom> {
+        match unwrap_expr(expr) {
+            Expr::Ident(i) => self.namespace_imports.get(&i.to_id()),
+            Expr::Member(MemberExpr {
+                obj,
+                prop: MemberProp::Ident(..),
+                ..
+            }) => self.namespace_source(obj),
+            _ => None,
+        }
+    }
 }
@@ -0,0 +1,74 @@
+use swc_core::common::{comments::Comments, BytePos, Span, Spanned};
+
+/// Skips the label and the refresh cache of the atoms in a declaration.
+pub const IGNORE_TAG: &str = "@jotai-ignore";
+/// Overrides the debug label of the atom in a declaration, e.g.
+/// `/* @jotai-label "Cart total" */`.
+pub const LABEL_TAG: &str = "@jotai-label";
+/// Leaves a whole file untouched when it starts with `/** @jotai-disable */`.
+pub const DISABLE_TAG: &str = "@jotai-disable";
+
+/// Returns the text following `tag` on its line in the leading comments at
+/// `pos`, e.g. `"Cart total"` for `/* @jotai-label "Cart total" */`.
+pub fn find_comment_tag(comments: &dyn Comments, pos: BytePos, tag: &str) -> Option<String> {
+    let leading = comments.get_leading(pos)?;
+    leading.iter().find_map(|comment| {
+        // Tags are separated by whitespace, or by the `*` of JSDoc comments.
+        let text = &*comment.text;
+        text.match_indices(tag).find_map(|(i, _)| {
+            let rest = &text[i + tag.len()..];
+            let is_separator = |c: char| c.is_whitespace() || c == '*';
+            let at_start = text[..i].chars().next_back().is_none_or(is_separator);
+            let at_end = rest.chars().next().is_none_or(is_separator);
+            (at_start && at_end).then(|| rest.lines().next().unwrap_or("").trim().to_string())
+        })
+    })
+}
+
+pub fn has_comment_tag(comments: &dyn Comments, pos: BytePos, tag: &str) -> bool {
+    find_comment_tag(comments, pos, tag).is_some()
+}
+
+/// Returns the text of a JSDoc `tag` in the leading comments at `pos`. The
+/// text runs until the next tag, so it may span several lines, which are
+/// joined with spaces, e.g. `auth` for `@atomGroup auth`.
+pub fn find_jsdoc_tag(comments: &dyn Comments, pos: BytePos, tag: &str) -> Option<String> {
+    let leading = comments.get_leading(pos)?;
+    leading.iter().find_map(|comment| {
+        let mut words = comment
+            .text
+            .lines()
+            .flat_map(|line| line.trim().trim_start_matches('*').split_whitespace());
+        words.find(|word| *word == tag)?;
+        let text = words
+            .take_while(|word| !word.starts_with('@'))
+            .collect::<Vec<_>>()
+            .join(" ");
+        (!text.is_empty()).then_some(text)
+    })
+}
+
+/// Returns the label of a `@jotai-label` tag at `pos`, with the quotes
+/// around it removed.
+pub fn find_label_tag(comments: &dyn Comments, pos: BytePos) -> Option<String> {
+    let label = find_comment_tag(comments, pos, LABEL_TAG)?;
+    let label = ['"', '\'', '`']
+        .iter()
+        .find_map(|&quote| label.strip_prefix(quote)?.strip_suffix(quote))
+        .unwrap_or(&label);
+    (!label.is_empty()).then(|| label.to_string())
+}
+
+/// Whether a program is disabled with a `@jotai-disable` tag at its start.
+pub fn is_file_disabled<T: Spanned>(comments: &dyn Comments, span: Span, body: &[T]) -> bool {
+    has_comment_tag(comments, span.lo, DISABLE_TAG)
+        || body
+            .first()
+            .is_some_and(|item| has_comment_tag(comments, item.span_lo(), DISABLE_TAG))
+}
+
+/// Like [`is_file_disabled`], for transforms that may run without comments,
+/// in which case no file is disabled.
+pub fn is_disabled<T: Spanned>(comments: Option<&dyn Comments>, span: Span, body: &[T]) -> bool {
+    comments.is_some_and(|comments| is_file_disabled(comments, span, body))
+}
@@ -1,14 +1,614 @@
-use serde::{Deserialize, Serialize};
-use swc_core::ecma::atoms::Atom;
+use std::{collections::BTreeMap, path::Path};
+
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{Map, Value};
+use swc_core::{
+    common::{errors::HANDLER, FileName},
+    ecma::atoms::Atom,
+};
+
+use crate::{ConfigError, Patterns, DEFAULT_IMPORT_SOURCES};
 
 /// Static plugin configuration.
-#[derive(Clone, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct Config {
     #[serde(default)]
-    pub atom_names: Vec<Atom>,
+    pub atom_names: Vec<AtomName>,
+    /// Module specifiers atom factories are imported from, either exact
+    /// specifiers or glob patterns such as `jotai/**`.
+    #[serde(default = "default_import_sources")]
+    pub import_sources: Vec<String>,
+    /// Additional atom factories keyed by the module specifier exporting
+    /// them. These modules are import sources as well.
+    #[serde(default)]
+    pub atom_factories: BTreeMap<String, Vec<Atom>>,
+    /// Restricts the built-in factory tables to one jotai major version.
+    #[serde(default)]
+    pub jotai_version: Option<JotaiVersion>,
+    /// Which module-level functions returning an atom factory call are
+    /// treated as atom factories themselves.
+    #[serde(default)]
+    pub detect_wrappers: DetectWrappers,
+    /// Whether an invalid config fails the build or only emits a warning and
+    /// falls back to the default config.
+    #[serde(default)]
+    pub on_config_error: OnConfigError,
+    /// Glob patterns of the files to transform. All files are transformed
+    /// when empty.
+    #[serde(default)]
+    pub include: Vec<String>,
+    /// Glob patterns of the files to leave untouched, e.g. `**/node_modules/**`.
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    /// Config fields applied on top of this config for matching files.
+    #[serde(default)]
+    pub overrides: Vec<ConfigOverride>,
+    /// Whether debug labels of atoms created in a function are prefixed with
+    /// the name of the function, e.g. `Counter.localAtom`.
+    #[serde(default)]
+    pub function_name_prefix: bool,
+    /// Template debug labels are expanded from. `[name]` is replaced by the
+    /// binding name, `[file]` by the file stem and `[dir]` by the directory
+    /// of the file relative to `root`.
+    #[serde(default = "default_label_template")]
+    pub label_template: String,
+    /// Project root that `[dir]` in `labelTemplate` is relative to. Relative
+    /// roots are resolved against the working directory, which is also the
+    /// default.
+    #[serde(default)]
+    pub root: Option<String>,
+    /// How debug labels are added to atoms.
+    #[serde(default)]
+    pub mode: DebugLabelMode,
+    /// How the parameter of an atom family member is serialized in its
+    /// label, e.g. `todoFamily("a")` or `todoFamily(a)`.
+    #[serde(default)]
+    pub family_param_format: FamilyParamFormat,
+    /// Glob patterns of binding names whose atoms are hidden from devtools
+    /// with `debugPrivate`, e.g. `_*`.
+    #[serde(default)]
+    pub private_atom_names: Vec<String>,
+    /// Whether atoms that aren't exported from the module are hidden from
+    /// devtools with `debugPrivate`.
+    #[serde(default)]
+    pub private_unexported: bool,
+    /// Whether atoms that are given a `debugLabel` in code are labeled
+    /// anyway, which the user's label then overwrites.
+    #[serde(default)]
+    pub overwrite_debug_labels: bool,
+    /// Whether atoms get a `{ fileName, line, column }` object pointing to
+    /// where they are created.
+    #[serde(default)]
+    pub debug_source: bool,
+    /// Name of the property `debugSource` is assigned to.
+    #[serde(default = "default_debug_source_property")]
+    pub debug_source_property: String,
+}
+
+/// Config of the preset plugin: the config shared with the other plugins,
+/// and which of its transforms run.
+#[derive(Clone, Debug)]
+pub struct PresetConfig {
+    pub config: Config,
+    /// Whether debug labels are added.
+    pub debug_label: bool,
+    /// Whether atoms are cached for React Refresh.
+    pub react_refresh: bool,
+}
+
+impl Default for PresetConfig {
+    fn default() -> Self {
+        PresetConfig {
+            config: Default::default(),
+            debug_label: true,
+            react_refresh: true,
+        }
+    }
+}
+
+/// Config fields that apply to a subset of files, such as one package of a
+/// monorepo.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ConfigOverride {
+    /// Glob patterns of the files the override applies to.
+    #[serde(deserialize_with = "one_or_many")]
+    pub files: Vec<String>,
+    /// Any other config field, replacing the value of the base config.
+    #[serde(flatten)]
+    pub config: Map<String, Value>,
+}
+
+fn one_or_many<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(one) => vec![one],
+        OneOrMany::Many(many) => many,
+    })
+}
+
+/// A custom atom factory name.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AtomName {
+    /// Matches any identifier with this name, imported or not.
+    Name(Atom),
+    /// Only matches `name` when it is imported from the module `from`.
+    Import { name: Atom, from: String },
+}
+
+impl From<&str> for AtomName {
+    fn from(name: &str) -> Self {
+        AtomName::Name(name.into())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JotaiVersion {
+    V1,
+    V2,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            atom_names: Default::default(),
+            import_sources: default_import_sources(),
+            atom_factories: Default::default(),
+            jotai_version: None,
+            detect_wrappers: Default::default(),
+            on_config_error: Default::default(),
+            include: Default::default(),
+            exclude: Default::default(),
+            overrides: Default::default(),
+            function_name_prefix: false,
+            label_template: default_label_template(),
+            root: None,
+            mode: Default::default(),
+            family_param_format: Default::default(),
+            private_atom_names: Vec::new(),
+            private_unexported: false,
+            overwrite_debug_labels: false,
+            debug_source: false,
+            debug_source_property: default_debug_source_property(),
+        }
+    }
+}
+
+impl Config {
+    /// Whether `include` and `exclude` allow transforming `file_name`. Paths
+    /// inside `cwd` are matched relative to it.
+    pub fn is_file_included(&self, file_name: &FileName, cwd: Option<&Path>) -> bool {
+        if self.include.is_empty() && self.exclude.is_empty() {
+            return true;
+        }
+
+        let Some(path) = match_path(file_name, cwd) else {
+            // Anonymous code can't match any pattern.
+            return self.include.is_empty();
+        };
+
+        (self.include.is_empty() || Patterns::new(&self.include).matches(&path))
+            && !Patterns::new(&self.exclude).matches(&path)
+    }
+
+    /// Returns the effective config for `file_name`, with every matching
+    /// override applied in order. Paths inside `cwd` are matched relative to
+    /// it.
+    pub fn for_file(&self, file_name: &FileName, cwd: Option<&Path>) -> Config {
+        let Some(path) = match_path(file_name, cwd) else {
+            return self.clone();
+        };
+        let matching: Vec<_> = self
+            .overrides
+            .iter()
+            .filter(|o| Patterns::new(&o.files).matches(&path))
+            .collect();
+        if matching.is_empty() {
+            return self.clone();
+        }
+
+        let mut config = serde_json::to_value(self).expect("Config is serializable");
+        for config_override in matching {
+            apply_override(&mut config, config_override);
+        }
+        serde_json::from_value(config).expect("overrides are validated when parsing the config")
+    }
+
+    /// Resolves `root` against `cwd`, defaulting to `cwd` itself.
+    pub fn resolve_root(&mut self, cwd: Option<&Path>) {
+        self.root = match (&self.root, cwd) {
+            (Some(root), Some(cwd)) => Some(cwd.join(root).to_string_lossy().into_owned()),
+            (None, Some(cwd)) => Some(cwd.to_string_lossy().into_owned()),
+            (root, None) => root.clone(),
+        };
+    }
+}
+
+/// Returns the path `include`, `exclude` and overrides are matched against.
+fn match_path(file_name: &FileName, cwd: Option<&Path>) -> Option<String> {
+    let FileName::Real(path) = file_name else {
+        return None;
+    };
+    Some(
+        cwd.and_then(|cwd| path.strip_prefix(cwd).ok())
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/"),
+    )
+}
+
+fn apply_override(config: &mut Value, config_override: &ConfigOverride) {
+    let Value::Object(config) = config else {
+        unreachable!("Config serializes to an object")
+    };
+    config.extend(config_override.config.clone());
+    // An applied config has no overrides left, so applying it again is a no-op.
+    config.insert("overrides".into(), Value::Array(Vec::new()));
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DetectWrappers {
+    Off,
+    /// Only wrappers that aren't exported from the module.
+    #[default]
+    Local,
+    /// Exported wrappers as well.
+    All,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DebugLabelMode {
+    /// `countAtom.debugLabel = "countAtom"` statements after the declaration.
+    #[default]
+    Statements,
+    /// `Object.assign(atom(0), { debugLabel: "countAtom" })` initializers,
+    /// which bundlers can still drop when the atom is unused.
+    Inline,
+    /// No labels, and the `debugLabel`, `debugPrivate`, `debugGroup` and
+    /// `debugDescription` assignments of atoms are removed, e.g. for
+    /// production builds.
+    Strip,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FamilyParamFormat {
+    /// `JSON.stringify(param)`
+    #[default]
+    Json,
+    /// `String(param)`
+    String,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OnConfigError {
+    #[default]
+    Error,
+    Warn,
 }
 
-pub fn parse_plugin_config(plugin_str: &str) -> Config {
-    serde_json::from_str::<Config>(plugin_str).expect("Invalid plugin config")
+fn default_label_template() -> String {
+    "[name]".into()
+}
+
+fn default_debug_source_property() -> String {
+    "debugSource".into()
+}
+
+fn default_import_sources() -> Vec<String> {
+    DEFAULT_IMPORT_SOURCES
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+}
+
+/// Parses the plugin config, reporting errors through swc's diagnostic
+/// handler instead of panicking. The default config is returned for an
+/// invalid config.
+pub fn parse_plugin_config(plugin_name: &str, plugin_str: &str) -> Config {
+    try_parse_plugin_config(plugin_str)
+        .unwrap_or_else(|error| report_config_error(plugin_name, plugin_str, error))
+}
+
+/// Parses the config of the preset plugin, like [`parse_plugin_config`].
+pub fn parse_preset_config(plugin_name: &str, plugin_str: &str) -> PresetConfig {
+    try_parse_preset_config(plugin_str)
+        .unwrap_or_else(|error| report_config_error(plugin_name, plugin_str, error))
+}
+
+fn report_config_error<T: Default>(plugin_name: &str, plugin_str: &str, error: ConfigError) -> T {
+    // Honor `onConfigError` even when another field is invalid.
+    let on_config_error = serde_json::from_str::<Value>(plugin_str)
+        .ok()
+        .and_then(|value| value.get("onConfigError").cloned())
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default();
+
+    HANDLER.with(|handler| {
+        let message = format!("{plugin_name}: invalid plugin config: {error}");
+        let mut diagnostic = match on_config_error {
+            OnConfigError::Error => handler.struct_err(&message),
+            OnConfigError::Warn => handler.struct_warn(&message),
+        };
+        if let Some(suggestion) = &error.suggestion {
+            diagnostic.help(&format!("did you mean `{suggestion}`?"));
+        }
+        if on_config_error == OnConfigError::Warn {
+            diagnostic.note("falling back to the default config");
+        }
+        diagnostic.emit();
+    });
+
+    T::default()
+}
+
+pub fn try_parse_plugin_config(plugin_str: &str) -> Result<Config, ConfigError> {
+    config_from_value(serde_json::from_str(plugin_str).map_err(ConfigError::from_json)?)
+}
+
+/// Parses the config of the preset plugin, whose `debugLabel` and
+/// `reactRefresh` fields turn its transforms off. The other plugins reject
+/// these fields.
+pub fn try_parse_preset_config(plugin_str: &str) -> Result<PresetConfig, ConfigError> {
+    let mut value: Value = serde_json::from_str(plugin_str).map_err(ConfigError::from_json)?;
+    let mut switch = |field: &str| match value.as_object_mut().and_then(|f| f.remove(field)) {
+        Some(enabled) => serde_json::from_value(enabled)
+            .map_err(|e| ConfigError::from_json(e).with_prefix(field)),
+        None => Ok(true),
+    };
+    let debug_label = switch("debugLabel")?;
+    let react_refresh = switch("reactRefresh")?;
+
+    Ok(PresetConfig {
+        config: config_from_value(value)?,
+        debug_label,
+        react_refresh,
+    })
+}
+
+fn config_from_value(value: Value) -> Result<Config, ConfigError> {
+    if let Value::Object(fields) = &value {
+        check_fields(fields)?;
+    }
+
+    let config: Config =
+        serde_path_to_error::deserialize(value).map_err(ConfigError::from_path_error)?;
+
+    // Validate overrides up front, so applying them per file can't fail.
+    for (i, config_override) in config.overrides.iter().enumerate() {
+        let prefix = format!("overrides[{i}]");
+        if config_override.config.contains_key("overrides") {
+            return Err(ConfigError::nested_overrides().with_prefix(&prefix));
+        }
+        check_fields(&config_override.config).map_err(|e| e.with_prefix(&prefix))?;
+
+        let mut value = serde_json::to_value(Config::default()).expect("Config is serializable");
+        apply_override(&mut value, config_override);
+        serde_path_to_error::deserialize::<_, Config>(value)
+            .map_err(|e| ConfigError::from_path_error(e).with_prefix(&prefix))?;
+    }
+
+    Ok(config)
+}
+
+/// Checks the field names ourselves to be able to suggest the closest valid
+/// field for an unknown one.
+fn check_fields(fields: &Map<String, Value>) -> Result<(), ConfigError> {
+    let Ok(Value::Object(known_fields)) = serde_json::to_value(Config::default()) else {
+        unreachable!("Config serializes to an object")
+    };
+    match fields.keys().find(|f| !known_fields.contains_key(*f)) {
+        Some(field) => Err(ConfigError::unknown_field(
+            field,
+            known_fields.keys().map(String::as_str),
+        )),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use swc_core::common::errors::{DiagnosticBuilder, Emitter, Handler, Level};
+
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct CollectingEmitter(Arc<Mutex<Vec<(Level, String)>>>);
+
+    impl Emitter for CollectingEmitter {
+        fn emit(&mut self, db: &mut DiagnosticBuilder<'_>) {
+            let mut message = db.message();
+            for child in &db.children {
+                message.push('\n');
+                message.push_str(&child.message());
+            }
+            self.0.lock().unwrap().push((db.level, message));
+        }
+    }
+
+    fn parse_with_handler(plugin_str: &str) -> (Config, Vec<(Level, String)>) {
+        let emitter = CollectingEmitter::default();
+        let handler = Handler::with_emitter(true, false, Box::new(emitter.clone()));
+        let config = HANDLER.set(&handler, || {
+            parse_plugin_config("@swc-jotai/test", plugin_str)
+        });
+        let diagnostics = emitter.0.lock().unwrap().clone();
+        (config, diagnostics)
+    }
+
+    fn is_file_included(config: &str, file_name: &str, cwd: Option<&str>) -> bool {
+        try_parse_plugin_config(config)
+            .unwrap()
+            .is_file_included(&FileName::Real(file_name.into()), cwd.map(Path::new))
+    }
+
+    #[test]
+    fn includes_all_files_by_default() {
+        assert!(is_file_included("{}", "node_modules/lib/index.js", None));
+        assert!(is_file_included("{}", "src/atoms.ts", None));
+    }
+
+    #[test]
+    fn include_and_exclude_globs() {
+        let config = r#"{ "include": ["src/**"], "exclude": ["**/*.test.ts"] }"#;
+        assert!(is_file_included(config, "src/atoms.ts", None));
+        assert!(is_file_included(config, "src/state/atoms.ts", None));
+        assert!(!is_file_included(config, "src/atoms.test.ts", None));
+        assert!(!is_file_included(config, "node_modules/lib/index.js", None));
+        assert!(!is_file_included(config, "generated/atoms.ts", None));
+    }
+
+    #[test]
+    fn matches_relative_to_cwd() {
+        let config = r#"{ "include": ["src/**"], "exclude": ["**/node_modules/**"] }"#;
+        assert!(is_file_included(config, "/app/src/atoms.ts", Some("/app")));
+        assert!(!is_file_included(config, "/app/src/atoms.ts", None));
+        assert!(!is_file_included(
+            config,
+            "/app/src/node_modules/lib/index.js",
+            Some("/app")
+        ));
+    }
+
+    #[test]
+    fn anonymous_files_only_match_without_include() {
+        let config = try_parse_plugin_config(r#"{ "exclude": ["**/*.test.ts"] }"#).unwrap();
+        assert!(config.is_file_included(&FileName::Anon, None));
+        let config = try_parse_plugin_config(r#"{ "include": ["src/**"] }"#).unwrap();
+        assert!(!config.is_file_included(&FileName::Anon, None));
+    }
+
+    #[test]
+    fn overrides_apply_to_matching_files() {
+        let config = try_parse_plugin_config(
+            r#"{
+                "atomNames": ["customAtom"],
+                "detectWrappers": "off",
+                "overrides": [
+                    { "files": "packages/editor/**", "atomNames": ["editorAtom"] },
+                    { "files": ["packages/*/src/legacy/**"], "jotaiVersion": "v1" }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let base = config.for_file(&FileName::Real("packages/app/src/atoms.ts".into()), None);
+        assert_eq!(base.atom_names, vec!["customAtom".into()]);
+        assert_eq!(base.jotai_version, None);
+
+        let editor = config.for_file(
+            &FileName::Real("/repo/packages/editor/src/legacy/atoms.ts".into()),
+            Some(Path::new("/repo")),
+        );
+        assert_eq!(editor.atom_names, vec!["editorAtom".into()]);
+        assert_eq!(editor.jotai_version, Some(JotaiVersion::V1));
+        assert_eq!(editor.detect_wrappers, DetectWrappers::Off);
+        assert!(editor.overrides.is_empty());
+    }
+
+    #[test]
+    fn resolves_root_against_cwd() {
+        let cwd = Path::new("/repo");
+        let mut config = Config::default();
+        config.resolve_root(Some(cwd));
+        assert_eq!(config.root.as_deref(), Some("/repo"));
+
+        let mut config = try_parse_plugin_config(r#"{ "root": "packages/app" }"#).unwrap();
+        config.resolve_root(Some(cwd));
+        assert_eq!(config.root.as_deref(), Some("/repo/packages/app"));
+
+        let mut config = try_parse_plugin_config(r#"{ "root": "/src" }"#).unwrap();
+        config.resolve_root(None);
+        assert_eq!(config.root.as_deref(), Some("/src"));
+    }
+
+    #[test]
+    fn invalid_override_names_field() {
+        let error = try_parse_plugin_config(
+            r#"{ "overrides": [{ "files": "a/**" }, { "files": "b/**", "atomName": [] }] }"#,
+        )
+        .unwrap_err();
+        assert_eq!(error.field.as_deref(), Some("overrides[1].atomName"));
+        assert_eq!(error.suggestion.as_deref(), Some("atomNames"));
+
+        let error =
+            try_parse_plugin_config(r#"{ "overrides": [{ "files": "a/**", "atomNames": [1] }] }"#)
+                .unwrap_err();
+        assert_eq!(error.field.as_deref(), Some("overrides[0].atomNames[0]"));
+    }
+
+    #[test]
+    fn unknown_field_suggests_closest_field() {
+        let error = try_parse_plugin_config(r#"{ "atomName": ["customAtom"] }"#).unwrap_err();
+        assert_eq!(error.field.as_deref(), Some("atomName"));
+        assert_eq!(error.suggestion.as_deref(), Some("atomNames"));
+    }
+
+    #[test]
+    fn unknown_field_without_close_match() {
+        let error = try_parse_plugin_config(r#"{ "foo": true }"#).unwrap_err();
+        assert_eq!(error.field.as_deref(), Some("foo"));
+        assert_eq!(error.suggestion, None);
+    }
+
+    #[test]
+    fn invalid_value_names_field() {
+        let error = try_parse_plugin_config(r#"{ "atomNames": ["customAtom", 1] }"#).unwrap_err();
+        assert_eq!(error.field.as_deref(), Some("atomNames[1]"));
+    }
+
+    #[test]
+    fn preset_switches() {
+        let config = try_parse_preset_config(r#"{ "reactRefresh": false }"#).unwrap();
+        assert!(config.debug_label);
+        assert!(!config.react_refresh);
+
+        let error = try_parse_preset_config(r#"{ "debugLabel": "no" }"#).unwrap_err();
+        assert_eq!(error.field.as_deref(), Some("debugLabel"));
+    }
+
+    #[test]
+    fn plugins_reject_preset_switches() {
+        let error = try_parse_plugin_config(r#"{ "debugLabel": false }"#).unwrap_err();
+        assert_eq!(error.field.as_deref(), Some("debugLabel"));
+    }
+
+    #[test]
+    fn reports_error_through_handler() {
+        let (config, diagnostics) = parse_with_handler(r#"{ "atomName": ["customAtom"] }"#);
+        assert!(config.atom_names.is_empty());
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].0, Level::Error);
+        assert_eq!(
+            diagnostics[0].1,
+            "@swc-jotai/test: invalid plugin config: `atomName`: unknown field\ndid you mean \
+             `atomNames`?"
+        );
+    }
+
+    #[test]
+    fn warns_and_falls_back_to_defaults() {
+        let (config, diagnostics) =
+            parse_with_handler(r#"{ "atomNames": 1, "onConfigError": "warn" }"#);
+        assert!(config.atom_names.is_empty());
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].0, Level::Warning);
+        assert!(diagnostics[0].1.contains("`atomNames`"));
+        assert!(diagnostics[0]
+            .1
+            .ends_with("falling back to the default config"));
+    }
 }
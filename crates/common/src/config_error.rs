@@ -0,0 +1,77 @@
+use std::fmt;
+
+/// An invalid plugin config.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConfigError {
+    /// Path to the offending field, e.g. `atomNames[0]`.
+    pub field: Option<String>,
+    pub message: String,
+    /// The closest valid field name for an unknown field.
+    pub suggestion: Option<String>,
+}
+
+impl ConfigError {
+    pub(crate) fn from_json(error: serde_json::Error) -> Self {
+        ConfigError {
+            field: None,
+            message: error.to_string(),
+            suggestion: None,
+        }
+    }
+
+    pub(crate) fn from_path_error(error: serde_path_to_error::Error<serde_json::Error>) -> Self {
+        let field = error.path().to_string();
+        ConfigError {
+            // The path of a top-level error is `.`.
+            field: (field != ".").then_some(field),
+            message: error.into_inner().to_string(),
+            suggestion: None,
+        }
+    }
+
+    pub(crate) fn unknown_field<'a>(
+        field: &str,
+        known_fields: impl Iterator<Item = &'a str>,
+    ) -> Self {
+        let suggestion = known_fields
+            .map(|known| (strsim::jaro_winkler(field, known), known))
+            .filter(|(similarity, _)| *similarity > 0.8)
+            .max_by(|(a, _), (b, _)| a.total_cmp(b))
+            .map(|(_, known)| known.to_string());
+
+        ConfigError {
+            field: Some(field.to_string()),
+            message: "unknown field".to_string(),
+            suggestion,
+        }
+    }
+
+    pub(crate) fn nested_overrides() -> Self {
+        ConfigError {
+            field: Some("overrides".to_string()),
+            message: "overrides can't be nested".to_string(),
+            suggestion: None,
+        }
+    }
+
+    /// Prefixes the field path, for errors inside a nested config object.
+    pub(crate) fn with_prefix(mut self, prefix: &str) -> Self {
+        self.field = Some(match self.field {
+            Some(field) if field.starts_with('[') => format!("{prefix}{field}"),
+            Some(field) => format!("{prefix}.{field}"),
+            None => prefix.to_string(),
+        });
+        self
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.field {
+            Some(field) => write!(f, "`{field}`: {}", self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
@@ -1,23 +1,107 @@
-pub static ATOM_IMPORTS: &[&str] = &[
-    "abortableAtom",
-    "atom",
+/// Atom factories exported by each jotai v1 entry point.
+pub static JOTAI_V1_FACTORIES: &[(&str, &[&str])] = &[
+    ("jotai", &["abortableAtom", "atom"]),
+    (
+        "jotai/utils",
+        &[
+            "atomFamily",
+            "atomWithDefault",
+            "atomWithHash",
+            "atomWithObservable",
+            "atomWithReducer",
+            "atomWithReset",
+            "atomWithStorage",
+            "freezeAtom",
+            "loadable",
+            "selectAtom",
+            "splitAtom",
+        ],
+    ),
+    ("jotai/immer", &["atomWithImmer"]),
+    ("jotai/optics", &["focusAtom"]),
+    ("jotai/query", &["atomWithInfiniteQuery", "atomWithQuery"]),
+    ("jotai/redux", &["atomWithStore"]),
+    (
+        "jotai/urql",
+        &["atomWithMutation", "atomWithQuery", "atomWithSubscription"],
+    ),
+    ("jotai/valtio", &["atomWithProxy"]),
+    ("jotai/xstate", &["atomWithMachine"]),
+    ("jotai/zustand", &["atomWithStore"]),
+];
+
+/// Atom factories exported by each jotai v2 entry point and the official
+/// integration packages.
+pub static JOTAI_V2_FACTORIES: &[(&str, &[&str])] = &[
+    ("jotai", &["atom"]),
+    ("jotai/vanilla", &["atom"]),
+    ("jotai/utils", JOTAI_V2_UTILS),
+    ("jotai/vanilla/utils", JOTAI_V2_UTILS),
+    ("jotai-cache", &["atomWithCache"]),
+    ("jotai-effect", &["atomEffect", "withAtomEffect"]),
+    ("jotai-family", &["atomFamily"]),
+    ("jotai-immer", &["atomWithImmer", "withImmer"]),
+    ("jotai-location", &["atomWithHash", "atomWithLocation"]),
+    ("jotai-optics", &["focusAtom"]),
+    ("jotai-redux", &["atomWithStore"]),
+    (
+        "jotai-tanstack-query",
+        &[
+            "atomWithInfiniteQuery",
+            "atomWithMutation",
+            "atomWithMutationState",
+            "atomWithQueries",
+            "atomWithQuery",
+            "atomWithSuspenseInfiniteQuery",
+            "atomWithSuspenseQuery",
+        ],
+    ),
+    (
+        "jotai-urql",
+        &["atomWithMutation", "atomWithQuery", "atomWithSubscription"],
+    ),
+    ("jotai-valtio", &["atomWithProxy", "mutableAtom"]),
+    (
+        "jotai-xstate",
+        &["atomWithActor", "atomWithActorSnapshot", "atomWithMachine"],
+    ),
+    ("jotai-zustand", &["atomWithStore"]),
+];
+
+static JOTAI_V2_UTILS: &[&str] = &[
     "atomFamily",
     "atomWithDefault",
-    "atomWithHash",
-    "atomWithImmer",
-    "atomWithInfiniteQuery",
-    "atomWithMachine",
-    "atomWithMutation",
+    "atomWithLazy",
     "atomWithObservable",
-    "atomWithProxy",
-    "atomWithQuery",
     "atomWithReducer",
+    "atomWithRefresh",
     "atomWithReset",
-    "atomWithSubscription",
     "atomWithStorage",
-    "atomWithStore",
     "freezeAtom",
     "loadable",
     "selectAtom",
     "splitAtom",
+    "unwrap",
+];
+
+/// Atom factories that return a family of atoms, created by calling the
+/// family with a parameter.
+pub static FAMILY_FACTORIES: &[&str] = &["atomFamily"];
+
+/// Modules atom factories are imported from unless `importSources` is set.
+pub static DEFAULT_IMPORT_SOURCES: &[&str] = &[
+    "jotai",
+    "jotai/**",
+    "jotai-cache",
+    "jotai-effect",
+    "jotai-family",
+    "jotai-immer",
+    "jotai-location",
+    "jotai-optics",
+    "jotai-redux",
+    "jotai-tanstack-query",
+    "jotai-urql",
+    "jotai-valtio",
+    "jotai-xstate",
+    "jotai-zustand",
 ];
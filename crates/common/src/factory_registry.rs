@@ -0,0 +1,88 @@
+use std::collections::{HashMap, HashSet};
+
+use swc_core::ecma::atoms::Atom;
+
+use crate::{AtomName, Config, JotaiVersion, JOTAI_V1_FACTORIES, JOTAI_V2_FACTORIES};
+
+/// Maps module specifiers to the atom factories they export.
+///
+/// Built from the built-in tables for the configured jotai version, extended
+/// with `atomFactories` and module bound `atomNames` from the plugin config.
+#[derive(Debug)]
+pub struct FactoryRegistry {
+    packages: HashMap<String, Vec<Atom>>,
+    /// Specifiers known to the built-in tables of any version.
+    builtin: HashSet<&'static str>,
+    /// Every factory name in the registry, used for import sources the
+    /// registry knows nothing about, such as a barrel re-exporting jotai.
+    all: Vec<Atom>,
+}
+
+impl FactoryRegistry {
+    pub fn new(config: &Config) -> Self {
+        let tables: &[&[(&str, &[&str])]] = match config.jotai_version {
+            Some(JotaiVersion::V1) => &[JOTAI_V1_FACTORIES],
+            Some(JotaiVersion::V2) => &[JOTAI_V2_FACTORIES],
+            None => &[JOTAI_V1_FACTORIES, JOTAI_V2_FACTORIES],
+        };
+
+        let mut packages: HashMap<String, Vec<Atom>> = HashMap::new();
+        for (package, factories) in tables.iter().flat_map(|t| t.iter()) {
+            extend(
+                &mut packages,
+                package,
+                factories.iter().map(|f| Atom::from(*f)),
+            );
+        }
+        for (package, factories) in &config.atom_factories {
+            extend(&mut packages, package, factories.iter().cloned());
+        }
+        for atom_name in &config.atom_names {
+            if let AtomName::Import { name, from } = atom_name {
+                extend(&mut packages, from, std::iter::once(name.clone()));
+            }
+        }
+
+        let mut all: Vec<Atom> = packages.values().flatten().cloned().collect();
+        all.sort();
+        all.dedup();
+
+        FactoryRegistry {
+            packages,
+            builtin: JOTAI_V1_FACTORIES
+                .iter()
+                .chain(JOTAI_V2_FACTORIES)
+                .map(|(package, _)| *package)
+                .collect(),
+            all,
+        }
+    }
+
+    /// Whether the module `src` is a package the registry has a table for.
+    pub fn is_known(&self, src: &str) -> bool {
+        self.packages.contains_key(src) || self.builtin.contains(src)
+    }
+
+    /// Returns the atom factories exported by the module `src`.
+    pub fn factories(&self, src: &str) -> &[Atom] {
+        match self.packages.get(src) {
+            Some(factories) => factories,
+            // A jotai entry point that the configured version doesn't have.
+            None if self.builtin.contains(src) => &[],
+            None => &self.all,
+        }
+    }
+}
+
+fn extend(
+    packages: &mut HashMap<String, Vec<Atom>>,
+    package: &str,
+    factories: impl Iterator<Item = Atom>,
+) {
+    let entry = packages.entry(package.to_string()).or_default();
+    for factory in factories {
+        if !entry.contains(&factory) {
+            entry.push(factory);
+        }
+    }
+}
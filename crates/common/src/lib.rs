@@ -1,7 +1,27 @@
 mod atom_import_map;
+mod comments;
 mod config;
+mod config_error;
 mod constants;
+mod factory_registry;
+mod patterns;
+mod unwrap;
+mod wrappers;
 
 pub use atom_import_map::AtomImportMap;
-pub use config::{parse_plugin_config, Config};
-pub use constants::ATOM_IMPORTS;
+pub use comments::{
+    find_comment_tag, find_jsdoc_tag, find_label_tag, has_comment_tag, is_disabled,
+    is_file_disabled, DISABLE_TAG, IGNORE_TAG, LABEL_TAG,
+};
+pub use config::{
+    parse_plugin_config, parse_preset_config, try_parse_plugin_config, try_parse_preset_config,
+    AtomName, Config, ConfigOverride, DebugLabelMode, DetectWrappers, FamilyParamFormat,
+    JotaiVersion, OnConfigError, PresetConfig,
+};
+pub use config_error::ConfigError;
+pub use constants::{
+    DEFAULT_IMPORT_SOURCES, FAMILY_FACTORIES, JOTAI_V1_FACTORIES, JOTAI_V2_FACTORIES,
+};
+pub use factory_registry::FactoryRegistry;
+pub use patterns::Patterns;
+pub use unwrap::{unwrap_expr, unwrap_expr_mut};
@@ -0,0 +1,39 @@
+use glob::{MatchOptions, Pattern};
+
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// A list of glob patterns.
+///
+/// `/` is matched literally, so `*` stays within one path segment and `**`
+/// is needed to match across segments. Entries that aren't valid globs are
+/// matched verbatim.
+#[derive(Clone, Debug, Default)]
+pub struct Patterns(Vec<Pattern>);
+
+impl Patterns {
+    pub fn new<S: AsRef<str>>(patterns: &[S]) -> Self {
+        Patterns(
+            patterns
+                .iter()
+                .map(|p| {
+                    let p = p.as_ref();
+                    Pattern::new(p).unwrap_or_else(|_| {
+                        Pattern::new(&Pattern::escape(p)).expect("escaped pattern is valid")
+                    })
+                })
+                .collect(),
+        )
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn matches(&self, value: &str) -> bool {
+        self.0.iter().any(|p| p.matches_with(value, MATCH_OPTIONS))
+    }
+}
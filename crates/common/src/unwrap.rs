@@ -0,0 +1,32 @@
+use swc_core::ecma::ast::*;
+
+/// Sees through wrappers that don't change the value of an expression:
+/// parentheses and the TypeScript `as`, `satisfies`, non-null, type assertion
+/// and instantiation expressions. For instance, `(atom<number>(0)!) as Atom`
+/// unwraps to `atom<number>(0)`.
+pub fn unwrap_expr(expr: &Expr) -> &Expr {
+    expr.unwrap_with(|e| match e {
+        Expr::Paren(ParenExpr { expr, .. })
+        | Expr::TsAs(TsAsExpr { expr, .. })
+        | Expr::TsSatisfies(TsSatisfiesExpr { expr, .. })
+        | Expr::TsNonNull(TsNonNullExpr { expr, .. })
+        | Expr::TsTypeAssertion(TsTypeAssertion { expr, .. })
+        | Expr::TsConstAssertion(TsConstAssertion { expr, .. })
+        | Expr::TsInstantiation(TsInstantiation { expr, .. }) => Some(expr),
+        _ => None,
+    })
+}
+
+/// Mutable version of [`unwrap_expr`].
+pub fn unwrap_expr_mut(expr: &mut Expr) -> &mut Expr {
+    expr.unwrap_mut_with(|e| match e {
+        Expr::Paren(ParenExpr { expr, .. })
+        | Expr::TsAs(TsAsExpr { expr, .. })
+        | Expr::TsSatisfies(TsSatisfiesExpr { expr, .. })
+        | Expr::TsNonNull(TsNonNullExpr { expr, .. })
+        | Expr::TsTypeAssertion(TsTypeAssertion { expr, .. })
+        | Expr::TsConstAssertion(TsConstAssertion { expr, .. })
+        | Expr::TsInstantiation(TsInstantiation { expr, .. }) => Some(expr),
+        _ => None,
+    })
+}
@@ -0,0 +1,142 @@
+use std::collections::HashSet;
+
+use swc_core::ecma::{
+    ast::*,
+    visit::{noop_visit_type, Visit, VisitWith},
+};
+
+use crate::{unwrap_expr, AtomImportMap};
+
+/// A module-level function that may wrap an atom factory.
+pub(crate) struct WrapperCandidate<'a> {
+    pub id: Id,
+    pub body: WrapperBody<'a>,
+    pub exported: bool,
+}
+
+pub(crate) enum WrapperBody<'a> {
+    Function(&'a Function),
+    Arrow(&'a ArrowExpr),
+}
+
+/// Collects the module-level function declarations and function valued
+/// `const`s of a module.
+pub(crate) fn collect_wrapper_candidates(module: &Module) -> Vec<WrapperCandidate<'_>> {
+    let mut candidates = Vec::new();
+    let mut exported_names = HashSet::new();
+
+    for item in &module.body {
+        match item {
+            ModuleItem::Stmt(Stmt::Decl(decl)) => collect_decl(decl, false, &mut candidates),
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { decl, .. })) => {
+                collect_decl(decl, true, &mut candidates)
+            }
+            // export { persisted };
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport {
+                specifiers,
+                src: None,
+                ..
+            })) => {
+                for s in specifiers {
+                    if let ExportSpecifier::Named(ExportNamedSpecifier {
+                        orig: ModuleExportName::Ident(orig),
+                        ..
+                    }) = s
+                    {
+                        exported_names.insert(orig.to_id());
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for candidate in &mut candidates {
+        candidate.exported |= exported_names.contains(&candidate.id);
+    }
+    candidates
+}
+
+fn collect_decl<'a>(decl: &'a Decl, exported: bool, candidates: &mut Vec<WrapperCandidate<'a>>) {
+    match decl {
+        // function persisted(init) { ... }
+        Decl::Fn(FnDecl {
+            ident, function, ..
+        }) => candidates.push(WrapperCandidate {
+            id: ident.to_id(),
+            body: WrapperBody::Function(function),
+            exported,
+        }),
+        // const persisted = (init) => ...;
+        Decl::Var(var_decl) => {
+            for decl in &var_decl.decls {
+                let (Pat::Ident(name), Some(init)) = (&decl.name, &decl.init) else {
+                    continue;
+                };
+                let body = match &**init {
+                    Expr::Arrow(arrow) => WrapperBody::Arrow(arrow),
+                    Expr::Fn(FnExpr { function, .. }) => WrapperBody::Function(function),
+                    _ => continue,
+                };
+                candidates.push(WrapperCandidate {
+                    id: name.to_id(),
+                    body,
+                    exported,
+                });
+            }
+        }
+        _ => {}
+    }
+}
+
+impl WrapperBody<'_> {
+    /// Whether the function returns the result of an atom factory call.
+    pub fn returns_atom(&self, atom_import_map: &AtomImportMap) -> bool {
+        let mut finder = AtomReturnFinder {
+            atom_import_map,
+            found: false,
+        };
+        match self {
+            WrapperBody::Function(Function {
+                body: Some(body), ..
+            }) => body.visit_children_with(&mut finder),
+            WrapperBody::Function(..) => {}
+            WrapperBody::Arrow(ArrowExpr { body, .. }) => match &**body {
+                BlockStmtOrExpr::BlockStmt(body) => body.visit_children_with(&mut finder),
+                BlockStmtOrExpr::Expr(expr) => finder.check(expr),
+                #[cfg(swc_ast_unknown)]
+                _ => {}
+            },
+        }
+        finder.found
+    }
+}
+
+struct AtomReturnFinder<'a> {
+    atom_import_map: &'a AtomImportMap,
+    found: bool,
+}
+
+impl AtomReturnFinder<'_> {
+    fn check(&mut self, expr: &Expr) {
+        self.found |= matches!(unwrap_expr(expr), Expr::Call(..))
+            && self.atom_import_map.is_atom_import(expr);
+    }
+}
+
+impl Visit for AtomReturnFinder<'_> {
+    noop_visit_type!();
+
+    fn visit_return_stmt(&mut self, return_stmt: &ReturnStmt) {
+        if let Some(arg) = &return_stmt.arg {
+            self.check(arg);
+        }
+    }
+
+    // Returns inside nested functions and classes belong to those.
+    fn visit_function(&mut self, _: &Function) {}
+
+    fn visit_arrow_expr(&mut self, _: &ArrowExpr) {}
+
+    fn visit_class(&mut self, _: &Class) {}
+}
@@ -1,19 +1,38 @@
-use std::{fs::read_to_string, path::PathBuf};
+use std::{
+    fs::read_to_string,
+    path::{Path, PathBuf},
+};
 
-use common::parse_plugin_config;
+use common::{parse_plugin_config, Config};
 use swc_core::{
-    common::FileName,
-    ecma::parser::{EsSyntax, Syntax},
-    ecma::transforms::testing::test_fixture,
+    common::{FileName, Mark},
+    ecma::parser::{EsSyntax, Syntax, TsSyntax},
+    ecma::transforms::{base::resolver, testing::test_fixture},
+    ecma::visit::visit_mut_pass,
 };
-use swc_jotai_debug_label::debug_label;
+use swc_jotai_debug_label::DebugLabelTransformVisitor;
 use testing::fixture;
 
-#[fixture("tests/fixtures/**/input.js")]
-fn test(input: PathBuf) {
+/// Fixtures are transformed as `src/atoms.ts` in this working directory.
+const CWD: &str = "/project";
+
+fn file_name() -> FileName {
+    FileName::Real(Path::new(CWD).join("src/atoms.ts"))
+}
+
+/// Resolves the config for the fixture file, like the plugin does.
+fn read_config(input: &Path) -> Config {
     let config =
         read_to_string(input.with_file_name("config.json")).expect("Failed to read config.json");
-    let config = parse_plugin_config(&config);
+    let config = parse_plugin_config("@swc-jotai/debug-label", &config);
+    let mut config = config.for_file(&file_name(), Some(Path::new(CWD)));
+    config.resolve_root(Some(Path::new(CWD)));
+    config
+}
+
+#[fixture("tests/fixtures/**/input.js")]
+fn test(input: PathBuf) {
+    let config = read_config(&input);
     let output = input.with_file_name("output.js");
 
     test_fixture(
@@ -21,7 +40,40 @@ fn test(input: PathBuf) {
             jsx: true,
             ..Default::default()
         }),
-        &|_t| debug_label(config.clone(), FileName::Real("atoms.ts".parse().unwrap())),
+        &|t| {
+            (
+                resolver(Mark::new(), Mark::new(), false),
+                visit_mut_pass(
+                    DebugLabelTransformVisitor::new(config.clone(), file_name())
+                        .with_source_map(t.cm.clone())
+                        .with_comments(t.comments.clone()),
+                ),
+            )
+        },
+        &input,
+        &output,
+        Default::default(),
+    )
+}
+
+#[fixture("tests/fixtures/**/input.ts")]
+fn test_typescript(input: PathBuf) {
+    let config = read_config(&input);
+    let output = input.with_file_name("output.ts");
+
+    test_fixture(
+        // Not TSX, so fixtures can use `<T>expr` type assertions.
+        Syntax::Typescript(TsSyntax::default()),
+        &|t| {
+            (
+                resolver(Mark::new(), Mark::new(), true),
+                visit_mut_pass(
+                    DebugLabelTransformVisitor::new(config.clone(), file_name())
+                        .with_source_map(t.cm.clone())
+                        .with_comments(t.comments.clone()),
+                ),
+            )
+        },
         &input,
         &output,
         Default::default(),
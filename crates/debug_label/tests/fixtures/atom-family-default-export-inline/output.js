@@ -0,0 +1,17 @@
+import { atom } from "jotai";
+import { atomFamily } from "jotai/utils";
+export default /*#__PURE__*/ Object.assign(atomFamily((id)=>Object.assign(atom({
+        id,
+        done: false
+    }), {
+        debugLabel: "atoms(" + serializeFamilyParam(id) + ")"
+    })), {
+    debugLabel: "atoms"
+});
+function serializeFamilyParam(param) {
+    try {
+        return JSON.stringify(param);
+    } catch  {
+        return String(param);
+    }
+}
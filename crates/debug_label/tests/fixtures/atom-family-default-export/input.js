@@ -0,0 +1,4 @@
+import { atom } from "jotai";
+import { atomFamily } from "jotai/utils";
+
+export default atomFamily((id) => atom({ id, done: false }));
@@ -0,0 +1,17 @@
+import { atom } from "jotai";
+import { atomFamily } from "jotai/utils";
+const atoms = atomFamily((id)=>Object.assign(atom({
+        id,
+        done: false
+    }), {
+        debugLabel: "atoms(" + serializeFamilyParam(id) + ")"
+    }));
+atoms.debugLabel = "atoms";
+export default atoms;
+function serializeFamilyParam(param) {
+    try {
+        return JSON.stringify(param);
+    } catch  {
+        return String(param);
+    }
+}
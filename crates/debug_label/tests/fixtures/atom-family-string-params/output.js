@@ -0,0 +1,9 @@
+import { atom } from "jotai";
+import { atomFamily } from "jotai/utils";
+export const todoFamily = atomFamily((id)=>Object.assign(atom({
+        id,
+        done: false
+    }), {
+        debugLabel: "todoFamily(" + String(id) + ")"
+    }));
+todoFamily.debugLabel = "todoFamily";
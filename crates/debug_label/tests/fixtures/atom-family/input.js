@@ -0,0 +1,14 @@
+import { atom } from "jotai";
+import { atomFamily } from "jotai/utils";
+import { atomFamily as family } from "jotai-family";
+import * as utils from "jotai/utils";
+
+const createTodoAtom = (id) => atom({ id, done: false });
+
+export const todoFamily = atomFamily((id) => atom({ id, done: false }));
+export const countFamily = family((key) => atom(0), (a, b) => a === b);
+export const userFamily = utils.atomFamily((userId) => atom(null));
+export const createdFamily = atomFamily(createTodoAtom);
+
+// @jotai-ignore
+export const ignoredFamily = atomFamily((id) => atom(id));
@@ -0,0 +1,36 @@
+import { atom } from "jotai";
+import { atomFamily } from "jotai/utils";
+import { atomFamily as family } from "jotai-family";
+import * as utils from "jotai/utils";
+const createTodoAtom = (id)=>atom({
+        id,
+        done: false
+    });
+export const todoFamily = atomFamily((id)=>Object.assign(atom({
+        id,
+        done: false
+    }), {
+        debugLabel: "todoFamily(" + serializeFamilyParam(id) + ")"
+    }));
+todoFamily.debugLabel = "todoFamily";
+export const countFamily = family((key)=>Object.assign(atom(0), {
+        debugLabel: "countFamily(" + serializeFamilyParam(key) + ")"
+    }), (a, b)=>a === b);
+countFamily.debugLabel = "countFamily";
+export const userFamily = utils.atomFamily((userId)=>Object.assign(atom(null), {
+        debugLabel: "userFamily(" + serializeFamilyParam(userId) + ")"
+    }));
+userFamily.debugLabel = "userFamily";
+export const createdFamily = atomFamily((param)=>Object.assign(createTodoAtom(param), {
+        debugLabel: "createdFamily(" + serializeFamilyParam(param) + ")"
+    }));
+createdFamily.debugLabel = "createdFamily";
+// @jotai-ignore
+export const ignoredFamily = atomFamily((id)=>atom(id));
+function serializeFamilyParam(param) {
+    try {
+        return JSON.stringify(param);
+    } catch  {
+        return String(param);
+    }
+}
@@ -0,0 +1,10 @@
+import { atom } from "jotai";
+export function createAtom(init) {
+    return atom(init);
+}
+const createText = (init)=>atom(String(init));
+export { createText };
+const countAtom = createAtom(0);
+countAtom.debugLabel = "countAtom";
+const textAtom = createText("");
+textAtom.debugLabel = "textAtom";
@@ -0,0 +1,26 @@
+import { atom } from "jotai";
+import { atomWithStorage } from "jotai/utils";
+function persisted(key, init) {
+    return atomWithStorage(key, init);
+}
+const boxed = function(value) {
+    return atom({
+        value
+    });
+};
+const persistedCount = (key)=>persisted(key, 0);
+export function exportedWrapper() {
+    return atom(0);
+}
+function notAWrapper() {
+    const inner = ()=>atom(0);
+    return inner;
+}
+const themeAtom = persisted("theme", "light");
+themeAtom.debugLabel = "themeAtom";
+const boxAtom = boxed(1);
+boxAtom.debugLabel = "boxAtom";
+const countAtom = persistedCount("count");
+countAtom.debugLabel = "countAtom";
+const exportedAtom = exportedWrapper();
+const innerAtom = notAWrapper()();
@@ -0,0 +1,14 @@
+import { atom } from "jotai";
+import { atomWithStorage } from "jotai/utils";
+
+export const atoms = {
+  count: atom(0),
+  user: {
+    name: atom(""),
+    settings: [atomWithStorage("theme", "light"), atom(false)],
+  },
+  1: atom(1),
+  label: "atoms",
+};
+
+const derived = [atom((get) => get(atoms.count) * 2), (atom(null))];
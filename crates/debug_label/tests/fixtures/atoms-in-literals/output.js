@@ -0,0 +1,25 @@
+import { atom } from "jotai";
+import { atomWithStorage } from "jotai/utils";
+export const atoms = {
+    count: atom(0),
+    user: {
+        name: atom(""),
+        settings: [
+            atomWithStorage("theme", "light"),
+            atom(false)
+        ]
+    },
+    1: atom(1),
+    label: "atoms"
+};
+atoms.count.debugLabel = "atoms.count";
+atoms.user.name.debugLabel = "atoms.user.name";
+atoms.user.settings[0].debugLabel = "atoms.user.settings.0";
+atoms.user.settings[1].debugLabel = "atoms.user.settings.1";
+atoms[1].debugLabel = "atoms.1";
+const derived = [
+    atom((get)=>get(atoms.count) * 2),
+    atom(null)
+];
+derived[0].debugLabel = "derived.0";
+derived[1].debugLabel = "derived.1";
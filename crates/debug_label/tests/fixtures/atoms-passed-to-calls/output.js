@@ -0,0 +1,16 @@
+import { atom, useAtomValue } from "jotai";
+const countAtom = atom(0);
+countAtom.debugLabel = "countAtom";
+const wrapped = someFn(atom(0));
+export function Counter() {
+    const count = useAtomValue(atom(0));
+    return count;
+}
+for(let loopAtom = atom(1);;){
+    break;
+}
+for (const itemAtom of [
+    atom(2)
+]){
+    console.log(itemAtom);
+}
@@ -0,0 +1,6 @@
+import { atom } from "jotai";
+
+class CartStore {
+  static items = atom([]);
+  totalAtom = atom(0);
+}
@@ -0,0 +1,9 @@
+import { atom } from "jotai";
+class CartStore {
+    static items = /*#__PURE__*/ Object.assign(atom([]), {
+        debugLabel: "CartStore.items"
+    });
+    totalAtom = /*#__PURE__*/ Object.assign(atom(0), {
+        debugLabel: "CartStore.totalAtom"
+    });
+}
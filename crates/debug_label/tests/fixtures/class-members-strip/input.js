@@ -0,0 +1,8 @@
+import { atom } from "jotai";
+
+class CartStore {
+  static items = atom([]);
+  totalAtom = Object.assign(atom(0), { debugLabel: "total" });
+}
+
+CartStore.items.debugLabel = "items";
@@ -0,0 +1,5 @@
+import { atom } from "jotai";
+class CartStore {
+    static items = atom([]);
+    totalAtom = atom(0);
+}
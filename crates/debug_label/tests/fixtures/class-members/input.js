@@ -0,0 +1,40 @@
+import { atom } from "jotai";
+
+class CartStore {
+  static items = atom([]);
+  static filters = { search: atom("") };
+  static #secret = atom(0);
+  totalAtom = atom((get) => get(CartStore.items).length);
+
+  static {
+    this.countAtom = atom(0);
+    const localAtom = atom(0);
+  }
+
+  createAtom() {
+    return atom(0);
+  }
+}
+
+export const UserStore = class {
+  static user = atom(null);
+};
+
+export default class SessionStore {
+  // @jotai-ignore
+  static session = atom(null);
+  /* @private */
+  static token = atom("");
+}
+
+CartStore.items.debugLabel = "cart items";
+
+export const LoggedStore = withLogging(class {
+  static items = atom([]);
+});
+
+export const stores = {
+  cart: class {
+    static items = atom([]);
+  },
+};
@@ -0,0 +1,44 @@
+import { atom } from "jotai";
+class CartStore {
+    static items = atom([]);
+    static filters = {
+        search: atom("")
+    };
+    static #secret = /*#__PURE__*/ Object.assign(atom(0), {
+        debugLabel: "CartStore.#secret"
+    });
+    totalAtom = /*#__PURE__*/ Object.assign(atom((get)=>get(CartStore.items).length), {
+        debugLabel: "CartStore.totalAtom"
+    });
+    static{
+        this.countAtom = atom(0);
+        const localAtom = atom(0);
+        localAtom.debugLabel = "localAtom";
+    }
+    createAtom() {
+        return atom(0);
+    }
+}
+CartStore.filters.search.debugLabel = "CartStore.filters.search";
+CartStore.countAtom.debugLabel = "CartStore.countAtom";
+export const UserStore = class {
+    static user = atom(null);
+};
+UserStore.user.debugLabel = "UserStore.user";
+export default class SessionStore {
+    // @jotai-ignore
+    static session = atom(null);
+    /* @private */ static token = atom("");
+}
+SessionStore.token.debugLabel = "SessionStore.token";
+SessionStore.token.debugPrivate = true;
+CartStore.items.debugLabel = "cart items";
+export const LoggedStore = withLogging(class {
+    static items = atom([]);
+});
+export const stores = {
+    cart: class {
+        static items = atom([]);
+    }
+};
+stores.cart.items.debugLabel = "stores.cart.items";
@@ -0,0 +1,20 @@
+import { atom } from "jotai";
+// @jotai-ignore
+export const scratchAtom = atom(0);
+/* @jotai-label "Cart total" */ export const cartTotalAtom = atom(0);
+cartTotalAtom.debugLabel = "Cart total";
+/**
+ * Items in the cart.
+ * @jotai-label 'Cart items'
+ */ export const cartItemsAtom = atom([]);
+cartItemsAtom.debugLabel = "Cart items";
+export const countAtom = atom(0);
+countAtom.debugLabel = "countAtom";
+/* @jotai-label "Cart count" */ export const cartCountAtom = atom(0), cartSizeAtom = atom(0);
+cartCountAtom.debugLabel = "Cart count";
+cartSizeAtom.debugLabel = "cartSizeAtom";
+// @jotai-ignore
+export function useScratch() {
+    const localAtom = atom(0);
+    return localAtom;
+}
@@ -0,0 +1,6 @@
+import { createAtom } from "@/lib/state";
+const myCustomAtom = customAtom(0);
+myCustomAtom.debugLabel = "myCustomAtom";
+const countAtom = createAtom(0);
+countAtom.debugLabel = "countAtom";
+const textAtom = createText("");
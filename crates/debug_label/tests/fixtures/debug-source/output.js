@@ -0,0 +1,8 @@
+import { atom } from "jotai";
+export const countAtom = atom(0);
+countAtom.debugLabel = "countAtom";
+countAtom.debugSource = {
+    fileName: "src/atoms.ts",
+    line: 3,
+    column: 26
+};
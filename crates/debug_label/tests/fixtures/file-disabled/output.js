@@ -0,0 +1,2 @@
+/** @jotai-disable */ import { atom } from "jotai";
+export const countAtom = atom(0);
@@ -0,0 +1,19 @@
+import { atom, useAtom } from "jotai";
+import { useMemo } from "react";
+
+export default function Counter() {
+  const countAtom = useMemo(() => {
+    const localAtom = atom(0);
+    return localAtom;
+  }, []);
+  const [count] = useAtom(countAtom);
+  return <div>{count}</div>;
+}
+
+export const Profile = () => {
+  const atoms = useMemo(() => {
+    const profileAtoms = { name: atom(""), age: atom(0) };
+    return profileAtoms;
+  }, []);
+  return null;
+};
@@ -0,0 +1,23 @@
+import { atom, useAtom } from "jotai";
+import { useMemo } from "react";
+export default function Counter() {
+    const countAtom = useMemo(()=>{
+        const localAtom = atom(0);
+        localAtom.debugLabel = "Counter.localAtom";
+        return localAtom;
+    }, []);
+    const [count] = useAtom(countAtom);
+    return <div>{count}</div>;
+}
+export const Profile = ()=>{
+    const atoms = useMemo(()=>{
+        const profileAtoms = {
+            name: atom(""),
+            age: atom(0)
+        };
+        profileAtoms.name.debugLabel = "Profile.profileAtoms.name";
+        profileAtoms.age.debugLabel = "Profile.profileAtoms.age";
+        return profileAtoms;
+    }, []);
+    return null;
+};
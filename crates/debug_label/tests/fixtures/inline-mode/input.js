@@ -0,0 +1,22 @@
+import { atom } from "jotai";
+import { atomWithStorage, selectAtom } from "jotai/utils";
+
+export const countAtom = atom(0);
+/** @atomGroup settings */
+const themeAtom = atomWithStorage("theme", "light");
+const doubleAtom = selectAtom(atom(1), (count) => count * 2);
+
+const atoms = {
+  user: atom({ name: "" }),
+  todos: [atom([])],
+};
+
+// Already labeled, left as is.
+const textAtom = Object.assign(atom(""), { debugLabel: "text" });
+
+function createAtoms() {
+  const localAtom = atom(null);
+  return localAtom;
+}
+
+export default atom(true);
@@ -0,0 +1,37 @@
+import { atom } from "jotai";
+import { atomWithStorage, selectAtom } from "jotai/utils";
+export const countAtom = /*#__PURE__*/ Object.assign(atom(0), {
+    debugLabel: "countAtom"
+});
+/** @atomGroup settings */ const themeAtom = /*#__PURE__*/ Object.assign(atomWithStorage("theme", "light"), {
+    debugLabel: "themeAtom",
+    debugGroup: "settings"
+});
+const doubleAtom = /*#__PURE__*/ Object.assign(selectAtom(atom(1), (count)=>count * 2), {
+    debugLabel: "doubleAtom"
+});
+const atoms = {
+    user: /*#__PURE__*/ Object.assign(atom({
+        name: ""
+    }), {
+        debugLabel: "atoms.user"
+    }),
+    todos: [
+        /*#__PURE__*/ Object.assign(atom([]), {
+            debugLabel: "atoms.todos.0"
+        })
+    ]
+};
+// Already labeled, left as is.
+const textAtom = Object.assign(atom(""), {
+    debugLabel: "text"
+});
+function createAtoms() {
+    const localAtom = /*#__PURE__*/ Object.assign(atom(null), {
+        debugLabel: "localAtom"
+    });
+    return localAtom;
+}
+export default /*#__PURE__*/ Object.assign(atom(true), {
+    debugLabel: "atoms"
+});
@@ -0,0 +1,22 @@
+import { atom } from "jotai";
+
+/**
+ * @atomGroup auth
+ * @description The signed in user, or null
+ * before the session is restored.
+ */
+export const userAtom = atom(null);
+
+/** @atomGroup auth @description Session token */
+export const tokenAtom = atom("");
+
+/**
+ * Settings shown in the sidebar.
+ * @atomGroup settings
+ */
+export const settingsAtoms = {
+  theme: atom("light"),
+};
+
+/** @atomGroup misc */
+export default atom(0);
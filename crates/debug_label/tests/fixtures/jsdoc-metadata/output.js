@@ -0,0 +1,25 @@
+import { atom } from "jotai";
+/**
+ * @atomGroup auth
+ * @description The signed in user, or null
+ * before the session is restored.
+ */ export const userAtom = atom(null);
+userAtom.debugLabel = "userAtom";
+userAtom.debugGroup = "auth";
+userAtom.debugDescription = "The signed in user, or null before the session is restored.";
+/** @atomGroup auth @description Session token */ export const tokenAtom = atom("");
+tokenAtom.debugLabel = "tokenAtom";
+tokenAtom.debugGroup = "auth";
+tokenAtom.debugDescription = "Session token";
+/**
+ * Settings shown in the sidebar.
+ * @atomGroup settings
+ */ export const settingsAtoms = {
+    theme: atom("light")
+};
+settingsAtoms.theme.debugLabel = "settingsAtoms.theme";
+settingsAtoms.theme.debugGroup = "settings";
+const atoms = atom(0);
+atoms.debugLabel = "atoms";
+atoms.debugGroup = "misc";
+export default atoms;
@@ -0,0 +1,10 @@
+import { atom } from "jotai";
+
+export const countAtom = atom(0);
+
+export const atoms = [atom(""), { nested: atom(false) }];
+
+export function useLocalAtom() {
+  const localAtom = atom(0);
+  return localAtom;
+}
@@ -0,0 +1,16 @@
+import { atom } from "jotai";
+export const countAtom = atom(0);
+countAtom.debugLabel = "atoms/countAtom";
+export const atoms = [
+    atom(""),
+    {
+        nested: atom(false)
+    }
+];
+atoms[0].debugLabel = "atoms/atoms.0";
+atoms[1].nested.debugLabel = "atoms/atoms.1.nested";
+export function useLocalAtom() {
+    const localAtom = atom(0);
+    localAtom.debugLabel = "atoms/useLocalAtom.localAtom";
+    return localAtom;
+}
@@ -0,0 +1,10 @@
+import { atom } from "jotai";
+import { atomWithStorage } from "jotai/utils";
+let counter = 0, countAtom = atom(0), label = "count";
+countAtom.debugLabel = "countAtom";
+export const themeAtom = atomWithStorage("theme", "light"), defaultTheme = "light", fontSizeAtom = atom(16);
+themeAtom.debugLabel = "themeAtom";
+fontSizeAtom.debugLabel = "fontSizeAtom";
+var first = atom(1), second, third = atom(3);
+first.debugLabel = "first";
+third.debugLabel = "third";
@@ -0,0 +1,5 @@
+const myCustomAtom = customAtom(0);
+myCustomAtom.debugLabel = "myCustomAtom";
+const myLocalAtom = localAtom(0);
+myLocalAtom.debugLabel = "myLocalAtom";
+const myEditorAtom = editorAtom(0);
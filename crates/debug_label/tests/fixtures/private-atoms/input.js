@@ -0,0 +1,18 @@
+import { atom } from "jotai";
+
+const baseAtom = atom(0);
+export const countAtom = atom((get) => get(baseAtom));
+
+const textAtom = atom("");
+export { textAtom };
+
+/** @private */
+export const cacheAtom = atom(new Map());
+
+export const _internalAtom = atom(null);
+export const privateStateAtom = atom({});
+
+export function useLocalAtom() {
+  const localAtom = atom(0);
+  return localAtom;
+}
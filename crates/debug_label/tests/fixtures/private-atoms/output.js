@@ -0,0 +1,24 @@
+import { atom } from "jotai";
+const baseAtom = atom(0);
+baseAtom.debugLabel = "baseAtom";
+baseAtom.debugPrivate = true;
+export const countAtom = atom((get)=>get(baseAtom));
+countAtom.debugLabel = "countAtom";
+const textAtom = atom("");
+textAtom.debugLabel = "textAtom";
+export { textAtom };
+/** @private */ export const cacheAtom = atom(new Map());
+cacheAtom.debugLabel = "cacheAtom";
+cacheAtom.debugPrivate = true;
+export const _internalAtom = atom(null);
+_internalAtom.debugLabel = "_internalAtom";
+_internalAtom.debugPrivate = true;
+export const privateStateAtom = atom({});
+privateStateAtom.debugLabel = "privateStateAtom";
+privateStateAtom.debugPrivate = true;
+export function useLocalAtom() {
+    const localAtom = atom(0);
+    localAtom.debugLabel = "localAtom";
+    localAtom.debugPrivate = true;
+    return localAtom;
+}
@@ -0,0 +1,13 @@
+import { atom } from "jotai";
+
+export class Store {
+  static countAtom = atom(0);
+}
+
+class Cache {
+  static entriesAtom = atom([]);
+}
+
+export default class Settings {
+  static themeAtom = atom("light");
+}
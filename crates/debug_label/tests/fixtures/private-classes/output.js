@@ -0,0 +1,14 @@
+import { atom } from "jotai";
+export class Store {
+    static countAtom = atom(0);
+}
+Store.countAtom.debugLabel = "Store.countAtom";
+class Cache {
+    static entriesAtom = atom([]);
+}
+Cache.entriesAtom.debugLabel = "Cache.entriesAtom";
+Cache.entriesAtom.debugPrivate = true;
+export default class Settings {
+    static themeAtom = atom("light");
+}
+Settings.themeAtom.debugLabel = "Settings.themeAtom";
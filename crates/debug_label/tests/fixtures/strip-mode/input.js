@@ -0,0 +1,34 @@
+import { atom } from "jotai";
+import { atomWithStorage } from "jotai/utils";
+
+export const countAtom = atom(0);
+countAtom.debugLabel = "count";
+
+const atoms = {
+  user: atom({ name: "" }),
+  theme: atomWithStorage("theme", "light"),
+};
+atoms.user.debugLabel = "user";
+atoms.theme.debugPrivate = true;
+atoms.theme.debugGroup = "settings";
+
+export const textAtom = /*#__PURE__*/ Object.assign(atom(""), {
+  debugLabel: "text",
+});
+export const flagAtom = Object.assign(atom(false), {
+  debugLabel: "flag",
+  debugPrivate: true,
+  toggle: () => {},
+});
+
+if (process.env.NODE_ENV !== "production") countAtom.debugPrivate = false;
+
+function setup() {
+  countAtom.debugLabel = "counter";
+}
+
+// Not an atom, left as is.
+const logger = {};
+logger.debugLabel = "logger";
+
+export default atom(true);
@@ -0,0 +1,19 @@
+import { atom } from "jotai";
+import { atomWithStorage } from "jotai/utils";
+export const countAtom = atom(0);
+const atoms = {
+    user: atom({
+        name: ""
+    }),
+    theme: atomWithStorage("theme", "light")
+};
+export const textAtom = atom("");
+export const flagAtom = Object.assign(atom(false), {
+    toggle: ()=>{}
+});
+if (process.env.NODE_ENV !== "production") ;
+function setup() {}
+// Not an atom, left as is.
+const logger = {};
+logger.debugLabel = "logger";
+export default atom(true);
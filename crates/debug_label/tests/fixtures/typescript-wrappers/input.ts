@@ -0,0 +1,21 @@
+import { atom, type Atom, type PrimitiveAtom } from "jotai";
+
+export const asAtom = atom(0) as PrimitiveAtom<number>;
+export const satisfiesAtom = atom(0) satisfies Atom<number>;
+export const nonNullAtom = atom<number>(0)!;
+export const assertionAtom = <PrimitiveAtom<number>>atom(0);
+export const parenAtom = (atom(0));
+export const instantiationAtom = (atom<string>)("");
+
+export const atoms = {
+  count: atom(0) as PrimitiveAtom<number>,
+  list: [(atom("")) satisfies Atom<string>],
+} as const;
+
+function boxed<T>(value: T) {
+  return atom({ value }) as PrimitiveAtom<{ value: T }>;
+}
+
+export const boxAtom = boxed(1);
+
+export default atom(0) as PrimitiveAtom<number>;
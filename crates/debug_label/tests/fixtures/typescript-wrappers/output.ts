@@ -0,0 +1,33 @@
+import { atom, type Atom, type PrimitiveAtom } from "jotai";
+export const asAtom = atom(0) as PrimitiveAtom<number>;
+asAtom.debugLabel = "asAtom";
+export const satisfiesAtom = atom(0) satisfies Atom<number>;
+satisfiesAtom.debugLabel = "satisfiesAtom";
+export const nonNullAtom = atom<number>(0)!;
+nonNullAtom.debugLabel = "nonNullAtom";
+export const assertionAtom = <PrimitiveAtom<number>>atom(0);
+assertionAtom.debugLabel = "assertionAtom";
+export const parenAtom = atom(0);
+parenAtom.debugLabel = "parenAtom";
+export const instantiationAtom = atom<string>("");
+instantiationAtom.debugLabel = "instantiationAtom";
+export const atoms = {
+    count: atom(0) as PrimitiveAtom<number>,
+    list: [
+        atom("") satisfies Atom<string>
+    ]
+} as const;
+atoms.count.debugLabel = "atoms.count";
+atoms.list[0].debugLabel = "atoms.list.0";
+function boxed<T>(value: T) {
+    return atom({
+        value
+    }) as PrimitiveAtom<{
+        value: T;
+    }>;
+}
+export const boxAtom = boxed(1);
+boxAtom.debugLabel = "boxAtom";
+const atoms1 = atom(0) as PrimitiveAtom<number>;
+atoms1.debugLabel = "atoms";
+export default atoms1;
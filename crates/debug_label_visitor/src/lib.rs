@@ -0,0 +1,2717 @@
+use std::{mem, path::Path, rc::Rc};
+
+use common::{
+    find_jsdoc_tag, find_label_tag, has_comment_tag, is_disabled, unwrap_expr, unwrap_expr_mut,
+    AtomImportMap, Config, DebugLabelMode, FamilyParamFormat, Patterns, IGNORE_TAG,
+};
+use swc_core::{
+    common::{
+        comments::Comments, errors::SourceMapperDyn, sync::Lrc, util::take::Take, BytePos,
+        EqIgnoreSpan, FileName, Loc, Span, Spanned, SyntaxContext, DUMMY_SP,
+    },
+    ecma::{
+        ast::*,
+        atoms::Atom,
+        utils::{is_valid_prop_ident, private_ident, quote_ident, ModuleItemLike, StmtLike},
+        visit::{
+            noop_visit_mut_type, noop_visit_type, visit_mut_pass, Visit, VisitMut, VisitMutWith,
+            VisitWith,
+        },
+    },
+};
+
+pub struct DebugLabelTransformVisitor {
+    atom_import_map: Rc<AtomImportMap>,
+    current_var_declarator: Option<Id>,
+    /// Path from the current var declarator to the current expression when
+    /// walking object and array literals in its initializer. For instance,
+    /// when walking this initializer:
+    /// ```js
+    /// const atoms = { user: { name: atom("") } }
+    /// ```
+    /// the path will be `[user, name]` when visiting `atom("")`.
+    access_path: Vec<MemberProp>,
+    /// Var declarator and access path the static members of the current
+    /// class are reached from, e.g. `CartStore` for `class CartStore {}`, or
+    /// `None` for an anonymous class expression.
+    current_class: Option<(Id, Vec<MemberProp>)>,
+    /// Label of the current var declarator from a `@jotai-label` directive.
+    current_label: Option<String>,
+    /// Name of the innermost named function, used as a label prefix when
+    /// `function_name_prefix` is set.
+    current_function_name: Option<Atom>,
+    function_name_prefix: bool,
+    family_param_format: FamilyParamFormat,
+    label_template: LabelTemplate,
+    overwrite_debug_labels: bool,
+    mode: DebugLabelMode,
+    private_atom_names: Patterns,
+    private_unexported: bool,
+    /// Bindings exported from the module.
+    exports: ExportedBindings,
+    /// Comment directives of the current statement.
+    annotations: Annotations,
+    /// Atoms given a `debugLabel` in code, which are left unlabeled.
+    debug_label_assignments: DebugLabelAssignments,
+    /// Property the source location of atoms is assigned to, if enabled.
+    debug_source_property: Option<Atom>,
+    source_map: Option<Lrc<SourceMapperDyn>>,
+    /// `fileName` of the `debugSource` of atoms.
+    debug_source_file_name: String,
+    /// Comments to add `#__PURE__` annotations to in inline mode.
+    comments: Option<Rc<dyn Comments>>,
+    /// Debug label assignments to insert after the current statement, in
+    /// source order.
+    debug_label_exprs: Vec<Expr>,
+    /// Atoms whose debug labels are stripped, in strip mode.
+    stripped_atoms: Vec<Expr>,
+    /// Function serializing the parameters of family members in their
+    /// labels, declared once per module.
+    family_param_serializer: Option<Ident>,
+    file_name: FileName,
+}
+
+/// Collects the atoms that code assigns a `debugLabel` to, such as
+/// `countAtom` in `countAtom.debugLabel = "count"`.
+#[derive(Default)]
+pub struct DebugLabelAssignments(Vec<Expr>);
+
+impl DebugLabelAssignments {
+    fn contains(&self, atom_expr: &Expr) -> bool {
+        self.0.iter().any(|expr| expr.eq_ignore_span(atom_expr))
+    }
+}
+
+impl Visit for DebugLabelAssignments {
+    noop_visit_type!();
+
+    fn visit_assign_expr(&mut self, assign_expr: &AssignExpr) {
+        if let Some((obj, "debugLabel")) = member_assign_target(assign_expr) {
+            self.0.push(unwrap_expr(obj).clone());
+        }
+        assign_expr.visit_children_with(self);
+    }
+}
+
+/// Properties assigned to an atom next to its `debugLabel`.
+type DebugProps = Vec<(Atom, Expr)>;
+
+/// Comment directives above a statement.
+#[derive(Default)]
+struct Annotations {
+    /// `/** @private */`
+    private: bool,
+    /// `// @jotai-ignore`
+    ignore: bool,
+    /// `/* @jotai-label "Cart total" */`
+    label: Option<String>,
+    /// `/** @atomGroup auth */`
+    group: Option<String>,
+    /// `/** @description The signed in user */`
+    description: Option<String>,
+}
+
+/// Collects the bindings a module exports, such as `countAtom` in
+/// `export { countAtom }`.
+#[derive(Default)]
+pub struct ExportedBindings(Vec<Id>);
+
+impl Visit for ExportedBindings {
+    noop_visit_type!();
+
+    fn visit_export_decl(&mut self, export_decl: &ExportDecl) {
+        match &export_decl.decl {
+            Decl::Var(var_decl) => {
+                self.0
+                    .extend(var_decl.decls.iter().filter_map(|decl| match &decl.name {
+                        Pat::Ident(ident) => Some(ident.to_id()),
+                        _ => None,
+                    }));
+            }
+            // The static atoms of exported classes are exported as well.
+            Decl::Class(class_decl) => self.0.push(class_decl.ident.to_id()),
+            _ => {}
+        }
+    }
+
+    fn visit_export_default_decl(&mut self, export_default: &ExportDefaultDecl) {
+        if let DefaultDecl::Class(ClassExpr {
+            ident: Some(ident), ..
+        }) = &export_default.decl
+        {
+            self.0.push(ident.to_id());
+        }
+    }
+
+    fn visit_named_export(&mut self, named_export: &NamedExport) {
+        // Re-exports from other modules don't export local bindings.
+        if named_export.src.is_some() {
+            return;
+        }
+        self.0
+            .extend(named_export.specifiers.iter().filter_map(|s| match s {
+                ExportSpecifier::Named(ExportNamedSpecifier {
+                    orig: ModuleExportName::Ident(ident),
+                    ..
+                }) => Some(ident.to_id()),
+                _ => None,
+            }));
+    }
+
+    fn visit_export_default_expr(&mut self, export_default: &ExportDefaultExpr) {
+        if let Expr::Ident(ident) = unwrap_expr(&export_default.expr) {
+            self.0.push(ident.to_id());
+        }
+    }
+}
+
+/// Removes the `debugLabel`, `debugPrivate`, `debugGroup` and
+/// `debugDescription` assignments of atoms, such as
+/// `countAtom.debugLabel = "count"` and `Object.assign(atom(0), { debugLabel })`.
+pub struct DebugLabelStripper {
+    atom_import_map: Rc<AtomImportMap>,
+    /// The atoms declared in the module, e.g. `atoms.count`.
+    atoms: Vec<Expr>,
+}
+
+const STRIPPED_PROPS: &[&str] = &[
+    "debugLabel",
+    "debugPrivate",
+    "debugGroup",
+    "debugDescription",
+];
+
+impl DebugLabelStripper {
+    fn is_atom(&self, expr: &Expr) -> bool {
+        let expr = unwrap_expr(expr);
+        matches!(expr, Expr::Call(..)) && self.atom_import_map.is_atom_import(expr)
+            || self.atoms.iter().any(|atom| atom.eq_ignore_span(expr))
+    }
+
+    /// Whether `stmt` is a statement like `countAtom.debugLabel = "count"`.
+    fn is_stripped_stmt(&self, stmt: &Stmt) -> bool {
+        let Stmt::Expr(ExprStmt { expr, .. }) = stmt else {
+            return false;
+        };
+        let Expr::Assign(assign_expr) = &**expr else {
+            return false;
+        };
+        member_assign_target(assign_expr)
+            .is_some_and(|(obj, prop)| STRIPPED_PROPS.contains(&prop) && self.is_atom(obj))
+    }
+
+    fn strip_stmt_like<T: StmtLike + VisitMutWith<Self>>(&mut self, stmts: &mut Vec<T>) {
+        stmts.retain(|stmt| {
+            !stmt
+                .as_stmt()
+                .is_some_and(|stmt| self.is_stripped_stmt(stmt))
+        });
+        for stmt in stmts {
+            stmt.visit_mut_with(self);
+        }
+    }
+}
+
+impl VisitMut for DebugLabelStripper {
+    noop_visit_mut_type!();
+
+    fn visit_mut_expr(&mut self, expr: &mut Expr) {
+        expr.visit_mut_children_with(self);
+
+        // Handles: Object.assign(atom(0), { debugLabel: "count" })
+        let Expr::Call(call_expr) = expr else {
+            return;
+        };
+        if !is_object_assign(call_expr)
+            || call_expr
+                .args
+                .first()
+                .is_none_or(|arg| !self.is_atom(&arg.expr))
+        {
+            return;
+        }
+        let mut is_empty = true;
+        for arg in call_expr.args.iter_mut().skip(1) {
+            match unwrap_expr_mut(&mut arg.expr) {
+                Expr::Object(object) if arg.spread.is_none() => {
+                    object
+                        .props
+                        .retain(|prop| !is_prop_named(prop, STRIPPED_PROPS));
+                    is_empty &= object.props.is_empty();
+                }
+                _ => is_empty = false,
+            }
+        }
+        // `Object.assign(atom(0), {})` is the atom itself.
+        if is_empty {
+            *expr = *call_expr.args.swap_remove(0).expr;
+        }
+    }
+
+    fn visit_mut_stmt(&mut self, stmt: &mut Stmt) {
+        // Handles: if (dev) countAtom.debugLabel = "count";
+        if self.is_stripped_stmt(stmt) {
+            *stmt = Stmt::Empty(EmptyStmt { span: DUMMY_SP });
+            return;
+        }
+        stmt.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_module_items(&mut self, items: &mut Vec<ModuleItem>) {
+        self.strip_stmt_like(items);
+    }
+
+    fn visit_mut_stmts(&mut self, stmts: &mut Vec<Stmt>) {
+        self.strip_stmt_like(stmts);
+    }
+}
+
+/// Returns the object and the property assigned to in `obj.prop = value`.
+fn member_assign_target(assign_expr: &AssignExpr) -> Option<(&Expr, &str)> {
+    match &assign_expr.left {
+        AssignTarget::Simple(SimpleAssignTarget::Member(MemberExpr {
+            obj,
+            prop: MemberProp::Ident(prop),
+            ..
+        })) if assign_expr.op == op!("=") => Some((obj, &prop.sym)),
+        _ => None,
+    }
+}
+
+/// Returns the property and the value assigned in a static block statement
+/// like `this.items = atom([])`.
+fn this_assign_mut(stmt: &mut Stmt) -> Option<(MemberProp, &mut Box<Expr>)> {
+    let Stmt::Expr(ExprStmt { expr, .. }) = stmt else {
+        return None;
+    };
+    let Expr::Assign(AssignExpr {
+        op: op!("="),
+        left:
+            AssignTarget::Simple(SimpleAssignTarget::Member(MemberExpr {
+                obj,
+                prop: prop @ MemberProp::Ident(..),
+                ..
+            })),
+        right,
+        ..
+    }) = &mut **expr
+    else {
+        return None;
+    };
+    matches!(&**obj, Expr::This(..)).then(|| (prop.clone(), right))
+}
+
+/// Whether `call_expr` is `Object.assign(...)`.
+fn is_object_assign(call_expr: &CallExpr) -> bool {
+    let Callee::Expr(callee) = &call_expr.callee else {
+        return false;
+    };
+    matches!(
+        &**callee,
+        Expr::Member(MemberExpr {
+            obj,
+            prop: MemberProp::Ident(prop),
+            ..
+        }) if matches!(&**obj, Expr::Ident(obj) if obj.sym == "Object") && prop.sym == "assign"
+    )
+}
+
+/// Whether `prop` sets one of the properties `names` in an object literal.
+fn is_prop_named(prop: &PropOrSpread, names: &[&str]) -> bool {
+    let PropOrSpread::Prop(prop) = prop else {
+        return false;
+    };
+    match &**prop {
+        Prop::Shorthand(ident) => names.contains(&&*ident.sym),
+        Prop::KeyValue(KeyValueProp { key, .. }) => match key {
+            PropName::Ident(ident) => names.contains(&&*ident.sym),
+            PropName::Str(s) => s.value.as_str().is_some_and(|key| names.contains(&key)),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Whether `call_expr` is `Object.assign(target, ...)` with a source object
+/// setting `debugLabel`.
+fn is_object_assign_with_debug_label(call_expr: &CallExpr) -> bool {
+    is_object_assign(call_expr)
+        && call_expr
+            .args
+            .iter()
+            .skip(1)
+            .any(|arg| match unwrap_expr(&arg.expr) {
+                Expr::Object(object) => object
+                    .props
+                    .iter()
+                    .any(|prop| is_prop_named(prop, &["debugLabel"])),
+                _ => false,
+            })
+}
+
+/// Returns `path` relative to the `root` of `config`, if it is inside it.
+fn relative_to_root<'a>(config: &Config, path: &'a Path) -> &'a Path {
+    config
+        .root
+        .as_ref()
+        .and_then(|root| path.strip_prefix(root).ok())
+        .unwrap_or(path)
+}
+
+/// Returns the `fileName` of `debugSource`, relative to the `root` so that
+/// builds don't depend on where the project is checked out.
+fn debug_source_file_name(config: &Config, file_name: &FileName) -> String {
+    match file_name {
+        FileName::Real(path) => relative_to_root(config, path)
+            .to_string_lossy()
+            .replace('\\', "/"),
+        _ => file_name.to_string(),
+    }
+}
+
+/// A `labelTemplate` together with the values of its file placeholders.
+struct LabelTemplate {
+    template: String,
+    /// The `[dir]` and `[file]` values, unless the file name is unknown.
+    file: Option<(String, String)>,
+}
+
+impl LabelTemplate {
+    fn new(config: &Config, file_name: &FileName) -> Self {
+        let file = match file_name {
+            FileName::Real(path) => {
+                let path = relative_to_root(config, path);
+                let dir = path
+                    .parent()
+                    .map(|dir| dir.to_string_lossy().replace('\\', "/"))
+                    .unwrap_or_default();
+                let file = path
+                    .file_stem()
+                    .map(|file| file.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                Some((dir, file))
+            }
+            _ => None,
+        };
+        Self {
+            template: config.label_template.clone(),
+            file,
+        }
+    }
+
+    /// Expands the template for the binding `name`. Without a file name the
+    /// label is `name` itself.
+    fn expand(&self, name: &str) -> String {
+        let Some((dir, file)) = &self.file else {
+            return name.to_string();
+        };
+
+        // Placeholders are expanded in a single pass, as directory names such
+        // as `app/[name]` may contain placeholders themselves.
+        let mut label = String::with_capacity(self.template.len() + name.len());
+        let mut rest = self.template.as_str();
+        while let Some(c) = rest.chars().next() {
+            let (value, len) = if rest.starts_with("[name]") {
+                (name, "[name]".len())
+            } else if rest.starts_with("[file]") {
+                (file.as_str(), "[file]".len())
+            } else if rest.starts_with("[dir]") {
+                (dir.as_str(), "[dir]".len())
+            } else {
+                label.push(c);
+                rest = &rest[c.len_utf8()..];
+                continue;
+            };
+            label.push_str(value);
+            rest = &rest[len..];
+        }
+        label
+    }
+}
+
+fn create_debug_label_assign_expr(
+    atom_name_id: Id,
+    access_path: &[MemberProp],
+    debug_label: Expr,
+) -> Expr {
+    create_assign_expr(
+        atom_member_expr(atom_name_id, access_path),
+        "debugLabel".into(),
+        debug_label,
+    )
+}
+
+fn create_debug_label(
+    atom_name: &Atom,
+    access_path: &[MemberProp],
+    label_prefix: Option<&Atom>,
+    label_template: &LabelTemplate,
+) -> String {
+    let mut atom_name = match label_prefix {
+        Some(prefix) => format!("{prefix}.{atom_name}"),
+        None => atom_name.to_string(),
+    };
+    for prop in access_path {
+        atom_name.push('.');
+        atom_name.push_str(&show_member_prop(prop));
+    }
+    label_template.expand(&atom_name)
+}
+
+fn create_debug_source(file_name: &str, loc: &Loc) -> Expr {
+    Expr::Object(ObjectLit {
+        span: DUMMY_SP,
+        props: vec![
+            create_prop("fileName".into(), Expr::Lit(Lit::Str(file_name.into()))),
+            create_prop("line".into(), Expr::Lit(Lit::Num((loc.line as f64).into()))),
+            // Columns are 1-based like lines, as editors expect.
+            create_prop(
+                "column".into(),
+                Expr::Lit(Lit::Num((loc.col.0 as f64 + 1.0).into())),
+            ),
+        ],
+    })
+}
+
+fn create_prop(key: Atom, value: Expr) -> PropOrSpread {
+    PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+        key: PropName::Ident(key.into()),
+        value: Box::new(value),
+    })))
+}
+
+/// Returns `Object.assign(atom_expr, { debugLabel, ... })`, which labels the
+/// atom without a separate statement.
+fn create_inline_debug_label_expr(atom_expr: Expr, props: Vec<PropOrSpread>) -> Expr {
+    Expr::Call(CallExpr {
+        span: atom_expr.span(),
+        callee: Callee::Expr(Box::new(Expr::Member(MemberExpr {
+            obj: Box::new(Expr::Ident(quote_ident!("Object").into())),
+            prop: MemberProp::Ident("assign".into()),
+            span: DUMMY_SP,
+        }))),
+        args: vec![
+            atom_expr.into(),
+            Expr::Object(ObjectLit {
+                span: DUMMY_SP,
+                props,
+            })
+            .into(),
+        ],
+        ..Default::default()
+    })
+}
+
+/// Returns `String(param)`.
+fn string_call(param: Ident) -> Expr {
+    call(quote_ident!("String").into(), param)
+}
+
+/// Returns `callee(arg)`.
+fn call(callee: Ident, arg: Ident) -> Expr {
+    Expr::Call(CallExpr {
+        callee: Callee::Expr(Box::new(Expr::Ident(callee))),
+        args: vec![Expr::Ident(arg).into()],
+        ..Default::default()
+    })
+}
+
+/// Returns the function serializing family parameters with
+/// `JSON.stringify`, falling back to `String(param)` for values it throws on,
+/// such as a `BigInt` or a cyclic object, so that labeling never breaks the
+/// family:
+/// ```js
+/// function serializeFamilyParam(param) {
+///   try { return JSON.stringify(param) } catch { return String(param) }
+/// }
+/// ```
+fn family_param_serializer_decl(serializer: Ident) -> Stmt {
+    let param = private_ident!("param");
+    let json_stringify = Expr::Call(CallExpr {
+        callee: Callee::Expr(Box::new(Expr::Member(MemberExpr {
+            obj: Box::new(Expr::Ident(quote_ident!("JSON").into())),
+            prop: MemberProp::Ident("stringify".into()),
+            span: DUMMY_SP,
+        }))),
+        args: vec![Expr::Ident(param.clone()).into()],
+        ..Default::default()
+    });
+    let return_block = |arg: Expr| BlockStmt {
+        stmts: vec![Stmt::Return(ReturnStmt {
+            span: DUMMY_SP,
+            arg: Some(Box::new(arg)),
+        })],
+        ..Default::default()
+    };
+    let try_stmt = Stmt::Try(Box::new(TryStmt {
+        span: DUMMY_SP,
+        block: return_block(json_stringify),
+        handler: Some(CatchClause {
+            span: DUMMY_SP,
+            param: None,
+            body: return_block(string_call(param.clone())),
+        }),
+        finalizer: None,
+    }));
+    Stmt::Decl(Decl::Fn(FnDecl {
+        ident: serializer,
+        declare: false,
+        function: Box::new(Function {
+            params: vec![Param {
+                span: DUMMY_SP,
+                decorators: Vec::new(),
+                pat: Pat::Ident(param.into()),
+            }],
+            body: Some(BlockStmt {
+                stmts: vec![try_stmt],
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+    }))
+}
+
+/// Returns the expression referring to the atom at `access_path` in the
+/// variable `atom_name_id`, e.g. `atoms.user.name`.
+fn atom_member_expr(atom_name_id: Id, access_path: &[MemberProp]) -> Expr {
+    access_path
+        .iter()
+        .fold(Expr::Ident(atom_name_id.into()), |obj, prop| {
+            Expr::Member(MemberExpr {
+                obj: Box::new(obj),
+                prop: prop.clone(),
+                span: DUMMY_SP,
+            })
+        })
+}
+
+fn create_assign_expr(obj: Expr, prop: Atom, value: Expr) -> Expr {
+    Expr::Assign(AssignExpr {
+        left: AssignTarget::Simple(SimpleAssignTarget::Member(MemberExpr {
+            obj: Box::new(obj),
+            prop: MemberProp::Ident(prop.into()),
+            span: DUMMY_SP,
+        })),
+        right: Box::new(value),
+        op: op!("="),
+        span: DUMMY_SP,
+    })
+}
+
+/// Returns the name of the atom exported by default from `file_name`: the file
+/// stem, or the directory name for `index` files.
+fn default_export_name(file_name: &FileName) -> String {
+    let FileName::Real(path) = file_name else {
+        return "default_atom".into();
+    };
+    let file_stem = path.file_stem().map(|s| s.to_string_lossy().into_owned());
+    if file_stem.as_deref().is_some_and(|s| s != "index") {
+        return file_stem.unwrap();
+    }
+    path.parent()
+        .and_then(|dir| dir.file_name())
+        .map(|dir| dir.to_string_lossy().into_owned())
+        .or(file_stem)
+        .unwrap_or_else(|| "default_atom".into())
+}
+
+/// Turns `name` into a valid identifier, camel casing around characters that
+/// can't appear in one, e.g. `my-atoms` into `myAtoms`.
+fn to_ident_name(name: &str) -> String {
+    let mut ident = String::with_capacity(name.len());
+    let mut upper = false;
+    for c in name.chars() {
+        if !Ident::is_valid_continue(c) {
+            upper = !ident.is_empty();
+        } else if upper {
+            ident.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            ident.push(c);
+        }
+    }
+    if ident.is_empty() {
+        return "default_atom".into();
+    }
+    if !ident.starts_with(Ident::is_valid_start) || Ident::verify_symbol(&ident).is_err() {
+        ident.insert(0, '_');
+    }
+    ident
+}
+
+/// Returns the member access for an object literal key, or `None` if the key
+/// is computed.
+fn prop_name_to_member_prop(prop_name: &PropName) -> Option<MemberProp> {
+    match prop_name {
+        PropName::Ident(i) => Some(MemberProp::Ident(i.clone())),
+        PropName::Str(s) => match s.value.as_str() {
+            Some(value) if is_valid_prop_ident(value) => Some(MemberProp::Ident(value.into())),
+            _ => Some(computed_member_prop(Lit::Str(s.clone()))),
+        },
+        PropName::Num(n) => Some(computed_member_prop(Lit::Num(n.clone()))),
+        _ => None,
+    }
+}
+
+fn computed_member_prop(lit: Lit) -> MemberProp {
+    MemberProp::Computed(ComputedPropName {
+        span: DUMMY_SP,
+        expr: Box::new(Expr::Lit(lit)),
+    })
+}
+
+fn show_member_prop(prop: &MemberProp) -> String {
+    match prop {
+        MemberProp::Ident(i) => i.sym.to_string(),
+        MemberProp::Computed(ComputedPropName { expr, .. }) => match &**expr {
+            Expr::Lit(Lit::Str(s)) => s.value.to_string_lossy().to_string(),
+            Expr::Lit(Lit::Num(n)) => n.value.to_string(),
+            _ => unreachable!("access paths only contain literal keys"),
+        },
+        MemberProp::PrivateName(name) => format!("#{}", name.name),
+        #[cfg(swc_ast_unknown)]
+        _ => panic!("unknown node"),
+    }
+}
+
+impl DebugLabelTransformVisitor {
+    /// Creates a visitor for `file_name`, with `config` already resolved for
+    /// the file by [`Config::for_file`].
+    pub fn new(config: Config, file_name: FileName) -> Self {
+        let atom_import_map = Rc::new(AtomImportMap::new(&config));
+        Self::with_atom_import_map(&config, file_name, atom_import_map)
+    }
+
+    /// Creates a visitor sharing an [`AtomImportMap`] that the caller has
+    /// already filled in for the module.
+    pub fn with_atom_import_map(
+        config: &Config,
+        file_name: FileName,
+        atom_import_map: Rc<AtomImportMap>,
+    ) -> Self {
+        Self {
+            atom_import_map,
+            current_var_declarator: None,
+            access_path: Vec::new(),
+            current_class: None,
+            current_label: None,
+            current_function_name: None,
+            function_name_prefix: config.function_name_prefix,
+            family_param_format: config.family_param_format,
+            label_template: LabelTemplate::new(config, &file_name),
+            mode: config.mode,
+            private_atom_names: Patterns::new(&config.private_atom_names),
+            private_unexported: config.private_unexported,
+            exports: Default::default(),
+            annotations: Default::default(),
+            overwrite_debug_labels: config.overwrite_debug_labels,
+            debug_label_assignments: Default::default(),
+            debug_source_property: config
+                .debug_source
+                .then(|| config.debug_source_property.as_str().into()),
+            source_map: None,
+            debug_source_file_name: debug_source_file_name(config, &file_name),
+            comments: None,
+            debug_label_exprs: Vec::new(),
+            stripped_atoms: Vec::new(),
+            family_param_serializer: None,
+            file_name,
+        }
+    }
+
+    /// Collects the `debugLabel` assignments in `node`. The atoms they label
+    /// are left unlabeled, unless `overwrite_debug_labels` is set.
+    pub fn collect_debug_label_assignments<N>(&mut self, node: &N)
+    where
+        N: VisitWith<DebugLabelAssignments>,
+    {
+        if !self.overwrite_debug_labels {
+            node.visit_with(&mut self.debug_label_assignments);
+        }
+    }
+
+    /// Collects the bindings exported from `node`, for `private_unexported`.
+    pub fn collect_exports<N>(&mut self, node: &N)
+    where
+        N: VisitWith<ExportedBindings>,
+    {
+        if self.private_unexported {
+            node.visit_with(&mut self.exports);
+        }
+    }
+
+    /// Removes the debug label assignments of the atoms in `node`, which must
+    /// have been transformed first. Does nothing unless in strip mode.
+    pub fn strip_debug_labels<N>(&mut self, node: &mut N)
+    where
+        N: VisitMutWith<DebugLabelStripper>,
+    {
+        if self.mode != DebugLabelMode::Strip {
+            return;
+        }
+        node.visit_mut_with(&mut DebugLabelStripper {
+            atom_import_map: self.atom_import_map.clone(),
+            atoms: mem::take(&mut self.stripped_atoms),
+        });
+    }
+
+    /// Declares the function serializing family parameters at the end of
+    /// `stmts`, if any family member was labeled with it. Function
+    /// declarations are hoisted, so the members can be created anywhere in
+    /// the module.
+    pub fn insert_family_param_serializer<T: StmtLike>(&mut self, stmts: &mut Vec<T>) {
+        if let Some(serializer) = self.family_param_serializer.take() {
+            stmts.push(T::from(family_param_serializer_decl(serializer)));
+        }
+    }
+
+    /// Sets the source map `debugSource` locations are looked up in. No
+    /// locations are emitted without one.
+    pub fn with_source_map(mut self, source_map: Lrc<SourceMapperDyn>) -> Self {
+        self.source_map = Some(source_map);
+        self
+    }
+
+    /// Sets the comments directives are read from, and inline labels are
+    /// annotated as `#__PURE__` in.
+    pub fn with_comments(mut self, comments: Rc<dyn Comments>) -> Self {
+        self.comments = Some(comments);
+        self
+    }
+
+    /// Returns the property and value of the `debugSource` of an atom created
+    /// at `span`, if enabled.
+    fn create_debug_source(&self, span: Span) -> Option<(Atom, Expr)> {
+        let property = self.debug_source_property.clone()?;
+        let source_map = self.source_map.as_ref()?;
+        if span.is_dummy() {
+            return None;
+        }
+        let loc = source_map.lookup_char_pos(span.lo);
+        Some((
+            property,
+            create_debug_source(&self.debug_source_file_name, &loc),
+        ))
+    }
+
+    fn create_debug_source_assign_expr(
+        &self,
+        atom_name_id: Id,
+        access_path: &[MemberProp],
+        span: Span,
+    ) -> Option<Expr> {
+        let (property, debug_source) = self.create_debug_source(span)?;
+        Some(create_assign_expr(
+            atom_member_expr(atom_name_id, access_path),
+            property,
+            debug_source,
+        ))
+    }
+
+    /// Returns the properties an atom gets next to its `debugLabel`:
+    /// `debugPrivate` if it is hidden from devtools, and the `debugGroup` and
+    /// `debugDescription` from the JSDoc of its declaration.
+    fn create_debug_props(&self, annotations: &Annotations, private: bool) -> DebugProps {
+        let string = |value: &str| Expr::Lit(Lit::Str(value.into()));
+        let mut props = Vec::new();
+        if private {
+            props.push(("debugPrivate".into(), Expr::Lit(Lit::Bool(true.into()))));
+        }
+        if let Some(group) = &annotations.group {
+            props.push(("debugGroup".into(), string(group)));
+        }
+        if let Some(description) = &annotations.description {
+            props.push(("debugDescription".into(), string(description)));
+        }
+        props
+    }
+
+    /// Whether the atom bound to `atom_name` is hidden from devtools.
+    fn is_private(&self, atom_name: &Id) -> bool {
+        self.annotations.private
+            || self.private_atom_names.matches(&atom_name.0)
+            || self.private_unexported && !self.exports.0.contains(atom_name)
+    }
+
+    /// Returns the label of the atom at the current access path of
+    /// `atom_name`, unless overridden by a `@jotai-label` directive.
+    fn create_debug_label(&self, atom_name: &Id) -> String {
+        if let Some(label) = &self.current_label {
+            if self.access_path.is_empty() {
+                return label.clone();
+            }
+        }
+        let label_prefix = self
+            .current_function_name
+            .as_ref()
+            .filter(|_| self.function_name_prefix);
+        create_debug_label(
+            &atom_name.0,
+            &self.access_path,
+            label_prefix,
+            &self.label_template,
+        )
+    }
+
+    /// Labels the atoms created by `call_expr`, if it creates a family, after
+    /// the family and their parameter, e.g. `todoFamily("a")` for
+    /// `const todoFamily = atomFamily((id) => atom(id))`.
+    fn label_family_members(&mut self, call_expr: &mut CallExpr, family_label: &str) {
+        let is_family = match &call_expr.callee {
+            Callee::Expr(callee) => self.atom_import_map.is_family_import(callee),
+            _ => false,
+        };
+        if !is_family {
+            return;
+        }
+        let serializer = match self.family_param_format {
+            FamilyParamFormat::Json => Some(
+                self.family_param_serializer
+                    .get_or_insert_with(|| private_ident!("serializeFamilyParam"))
+                    .clone(),
+            ),
+            FamilyParamFormat::String => None,
+        };
+        let Some(ExprOrSpread {
+            spread: None,
+            expr: initializer,
+        }) = call_expr.args.first_mut()
+        else {
+            return;
+        };
+        let debug_label = |param: Ident| {
+            let serialized = match &serializer {
+                Some(serializer) => call(serializer.clone(), param),
+                None => string_call(param),
+            };
+            let concat = |left: Expr, right: Expr| {
+                Expr::Bin(BinExpr {
+                    span: DUMMY_SP,
+                    op: op!(bin, "+"),
+                    left: Box::new(left),
+                    right: Box::new(right),
+                })
+            };
+            let string = |value: String| Expr::Lit(Lit::Str(value.into()));
+            concat(
+                concat(string(format!("{family_label}(")), serialized),
+                string(")".into()),
+            )
+        };
+        let labeled = |atom_expr: Expr, param: Ident| {
+            create_inline_debug_label_expr(
+                atom_expr,
+                vec![create_prop("debugLabel".into(), debug_label(param))],
+            )
+        };
+
+        let initializer = unwrap_expr_mut(initializer);
+        // Handles: atomFamily((id) => atom(id))
+        if let Expr::Arrow(ArrowExpr { params, body, .. }) = initializer {
+            if let (Some(Pat::Ident(param)), BlockStmtOrExpr::Expr(body)) =
+                (params.first(), &mut **body)
+            {
+                **body = labeled(*body.take(), param.id.clone());
+                return;
+            }
+        }
+        // Handles: atomFamily(createTodoAtom)
+        let param = private_ident!("param");
+        let atom_expr = Expr::Call(CallExpr {
+            callee: Callee::Expr(Box::new(initializer.take())),
+            args: vec![Expr::Ident(param.clone()).into()],
+            ..Default::default()
+        });
+        *initializer = Expr::Arrow(ArrowExpr {
+            params: vec![Pat::Ident(param.clone().into())],
+            body: Box::new(BlockStmtOrExpr::Expr(Box::new(labeled(atom_expr, param)))),
+            ..Default::default()
+        });
+    }
+
+    /// Reads the comment directives of the statement at `pos`.
+    fn annotations_at(&self, pos: BytePos) -> Annotations {
+        let Some(comments) = self.comments.as_deref() else {
+            return Default::default();
+        };
+        Annotations {
+            private: has_comment_tag(comments, pos, "@private"),
+            ignore: has_comment_tag(comments, pos, IGNORE_TAG),
+            label: find_label_tag(comments, pos),
+            group: find_jsdoc_tag(comments, pos, "@atomGroup"),
+            description: find_jsdoc_tag(comments, pos, "@description"),
+        }
+    }
+
+    /// Visits a statement with its comment directives. Statements nested in
+    /// an ignored statement are ignored too.
+    fn visit_mut_annotated<N: VisitMutWith<Self> + Spanned>(&mut self, node: &mut N) {
+        let mut annotations = self.annotations_at(node.span_lo());
+        annotations.ignore |= self.annotations.ignore;
+        let outer_annotations = mem::replace(&mut self.annotations, annotations);
+        node.visit_mut_with(self);
+        self.annotations = outer_annotations;
+    }
+
+    /// Labels the atom created by `expr` in place, marking the labeled
+    /// expression as pure so that bundlers can drop unused atoms.
+    fn inline_debug_label(&self, expr: &mut Expr, debug_label: Expr, debug_props: DebugProps) {
+        let span = expr.span();
+        let mut props = vec![create_prop("debugLabel".into(), debug_label)];
+        props.extend(
+            debug_props
+                .into_iter()
+                .map(|(property, value)| create_prop(property, value)),
+        );
+        props.extend(
+            self.create_debug_source(span)
+                .map(|(property, debug_source)| create_prop(property, debug_source)),
+        );
+        *expr = create_inline_debug_label_expr(expr.take(), props);
+        if let Some(comments) = &self.comments {
+            if !span.is_dummy() {
+                comments.add_pure_comment(span.lo);
+            }
+        }
+    }
+
+    /// Labels the atom created by the initializer `expr` of the current var
+    /// declarator, in inline mode.
+    fn inline_var_declarator_debug_label(&self, expr: &mut Expr) {
+        let Some(atom_name) = &self.current_var_declarator else {
+            return;
+        };
+        if self.annotations.ignore
+            || !self.is_atom_call(expr)
+            || self
+                .debug_label_assignments
+                .contains(&atom_member_expr(atom_name.clone(), &self.access_path))
+        {
+            return;
+        }
+        let debug_label = Expr::Lit(Lit::Str(self.create_debug_label(atom_name).into()));
+        let debug_props = self.create_debug_props(&self.annotations, self.is_private(atom_name));
+        self.inline_debug_label(unwrap_expr_mut(expr), debug_label, debug_props);
+    }
+}
+
+impl DebugLabelTransformVisitor {
+    fn visit_mut_stmt_like<T>(&mut self, stmts: &mut Vec<T>)
+    where
+        T: VisitMutWith<Self> + StmtLike + ModuleItemLike,
+    {
+        let mut stmts_updated: Vec<T> = Vec::with_capacity(stmts.len());
+
+        for stmt in stmts.take() {
+            self.transform_stmt_like(stmt, &mut stmts_updated);
+        }
+
+        *stmts = stmts_updated;
+    }
+
+    /// Visits an initializer, recording the path to the atoms created in its
+    /// object and array literals.
+    fn visit_mut_atom_path(&mut self, expr: &mut Expr) {
+        let is_atom_call = self.is_atom_call(expr);
+        match unwrap_expr_mut(expr) {
+            Expr::Object(object) => {
+                for prop in object.props.iter_mut() {
+                    let prop = match prop {
+                        PropOrSpread::Prop(prop) => prop,
+                        PropOrSpread::Spread(SpreadElement { expr, .. }) => {
+                            self.visit_mut_without_declarator(expr);
+                            continue;
+                        }
+                        #[cfg(swc_ast_unknown)]
+                        _ => panic!("unknown node"),
+                    };
+                    match prop.as_mut() {
+                        Prop::KeyValue(KeyValueProp { key, value }) => {
+                            key.visit_mut_with(self);
+                            match prop_name_to_member_prop(key) {
+                                Some(member_prop) => {
+                                    self.access_path.push(member_prop);
+                                    self.visit_mut_atom_path(value);
+                                    self.access_path.pop();
+                                }
+                                None => self.visit_mut_without_declarator(value),
+                            }
+                        }
+                        _ => prop.visit_mut_with(self),
+                    }
+                }
+            }
+            Expr::Array(array) => {
+                let mut after_spread = false;
+                for (i, elem) in array.elems.iter_mut().enumerate() {
+                    let Some(elem) = elem else {
+                        continue;
+                    };
+                    // Elements after a spread don't have a known index.
+                    after_spread |= elem.spread.is_some();
+                    if after_spread {
+                        self.visit_mut_without_declarator(&mut elem.expr);
+                        continue;
+                    }
+                    self.access_path
+                        .push(computed_member_prop(Lit::Num(Number::from(i as f64))));
+                    self.visit_mut_atom_path(&mut elem.expr);
+                    self.access_path.pop();
+                }
+            }
+            // Handles: const CartStore = class { ... }
+            Expr::Class(ClassExpr { class, .. }) => {
+                let class_path = self
+                    .current_var_declarator
+                    .clone()
+                    .map(|id| (id, self.access_path.clone()));
+                self.visit_mut_class_with_path(class, class_path);
+            }
+            // Handles: const countAtom = atom(0)
+            _ if is_atom_call => {
+                expr.visit_mut_with(self);
+                if self.mode == DebugLabelMode::Inline {
+                    self.inline_var_declarator_debug_label(expr);
+                }
+            }
+            // Atoms passed to other calls, such as `useAtomValue(atom(0))`,
+            // aren't bound to the declarator.
+            _ => self.visit_mut_without_declarator(expr),
+        }
+    }
+
+    /// Whether `expr` is a call to an atom factory or a wrapper of one.
+    fn is_atom_call(&self, expr: &Expr) -> bool {
+        matches!(unwrap_expr(expr), Expr::Call(..)) && self.atom_import_map.is_atom_import(expr)
+    }
+
+    /// Visits the head of a `for` loop. Statements can't be inserted after a
+    /// declaration there, where its bindings are scoped to the loop, so its
+    /// atoms are left unlabeled.
+    fn visit_mut_loop_head<N: VisitMutWith<Self>>(&mut self, head: &mut N) {
+        let debug_label_exprs = mem::take(&mut self.debug_label_exprs);
+        head.visit_mut_with(self);
+        self.debug_label_exprs = debug_label_exprs;
+    }
+
+    /// Visits a class whose static members are reached from `class_path`.
+    fn visit_mut_class_with_path(
+        &mut self,
+        class: &mut Class,
+        class_path: Option<(Id, Vec<MemberProp>)>,
+    ) {
+        let old_class = mem::replace(&mut self.current_class, class_path);
+        let old_var_declarator = self.current_var_declarator.take();
+        let old_access_path = mem::take(&mut self.access_path);
+        class.visit_mut_children_with(self);
+        self.current_class = old_class;
+        self.current_var_declarator = old_var_declarator;
+        self.access_path = old_access_path;
+    }
+
+    /// Visits the value of the class member `prop` declared at `span`. Static
+    /// members are labeled like the atoms of a var declarator, e.g.
+    /// `CartStore.items`, while the atoms of other members are labeled where
+    /// they are created, as they can't be reached from outside the class.
+    fn visit_mut_class_member(
+        &mut self,
+        span: Span,
+        prop: MemberProp,
+        value: &mut Expr,
+        reachable: bool,
+    ) {
+        let Some((class_name, mut access_path)) = self.current_class.clone() else {
+            value.visit_mut_with(self);
+            return;
+        };
+        access_path.push(prop);
+        let mut annotations = self.annotations_at(span.lo);
+        annotations.ignore |= self.annotations.ignore;
+        let old_annotations = mem::replace(&mut self.annotations, annotations);
+        let old_var_declarator = self.current_var_declarator.replace(class_name);
+        let old_access_path = mem::replace(&mut self.access_path, access_path);
+
+        if reachable {
+            self.visit_mut_atom_path(value);
+        } else {
+            self.visit_mut_without_declarator(value);
+            if self.mode != DebugLabelMode::Strip {
+                self.inline_var_declarator_debug_label(value);
+            }
+        }
+
+        self.annotations = old_annotations;
+        self.current_var_declarator = old_var_declarator;
+        self.access_path = old_access_path;
+    }
+
+    /// Visits an expression whose atoms can't be reached from the var
+    /// declarator, such as the value of a computed key.
+    fn visit_mut_without_declarator(&mut self, expr: &mut Expr) {
+        let old_var_declarator = self.current_var_declarator.take();
+        expr.visit_mut_with(self);
+        self.current_var_declarator = old_var_declarator;
+    }
+
+    /// Transforms a single statement or module item, pushing it and the debug
+    /// label assignments following it to `out`.
+    pub fn transform_stmt_like<T>(&mut self, stmt: T, out: &mut Vec<T>)
+    where
+        T: VisitMutWith<Self> + StmtLike + ModuleItemLike,
+    {
+        // Statements nested in this one insert their own labels.
+        let outer_debug_label_exprs = mem::take(&mut self.debug_label_exprs);
+
+        let stmt = match stmt.try_into_stmt() {
+            Ok(mut stmt) => {
+                self.visit_mut_annotated(&mut stmt);
+                T::from(stmt)
+            }
+            Err(node) => match node.try_into_module_decl() {
+                Ok(mut module_decl) => {
+                    let annotations = self.annotations_at(module_decl.span_lo());
+                    match module_decl {
+                        ModuleDecl::ExportDefaultExpr(mut default_export)
+                            if self.mode == DebugLabelMode::Inline
+                                && !annotations.ignore
+                                && self.atom_import_map.is_atom_import(&default_export.expr) =>
+                        {
+                            default_export.visit_mut_children_with(self);
+                            let label_name = default_export_name(&self.file_name);
+                            let debug_props = self.create_debug_props(&annotations, false);
+                            let label = annotations
+                                .label
+                                .unwrap_or_else(|| self.label_template.expand(&label_name));
+                            if let Expr::Call(call_expr) = unwrap_expr_mut(&mut default_export.expr)
+                            {
+                                self.label_family_members(call_expr, &label);
+                            }
+                            self.inline_debug_label(
+                                unwrap_expr_mut(&mut default_export.expr),
+                                Expr::Lit(Lit::Str(label.into())),
+                                debug_props,
+                            );
+                            <T as ModuleItemLike>::try_from_module_decl(
+                                ModuleDecl::ExportDefaultExpr(default_export),
+                            )
+                            .unwrap()
+                        }
+                        ModuleDecl::ExportDefaultExpr(mut default_export)
+                            if self.mode == DebugLabelMode::Statements
+                                && !annotations.ignore
+                                && self.atom_import_map.is_atom_import(&default_export.expr) =>
+                        {
+                            let atom_span = default_export.expr.span();
+                            let debug_props = self.create_debug_props(&annotations, false);
+                            // The label keeps the file's name, while the binding is a
+                            // valid identifier that hygiene renames on collisions.
+                            let label_name = default_export_name(&self.file_name);
+                            let atom_ident = private_ident!(to_ident_name(&label_name));
+                            let label = annotations
+                                .label
+                                .unwrap_or_else(|| self.label_template.expand(&label_name));
+                            if let Expr::Call(call_expr) = unwrap_expr_mut(&mut default_export.expr)
+                            {
+                                self.label_family_members(call_expr, &label);
+                            }
+
+                            // Variable declaration
+                            out.push(T::from(Stmt::Decl(Decl::Var(Box::new(VarDecl {
+                                declare: Default::default(),
+                                decls: vec![VarDeclarator {
+                                    definite: false,
+                                    init: Some(default_export.expr),
+                                    name: Pat::Ident(atom_ident.clone().into()),
+                                    span: DUMMY_SP,
+                                }],
+                                kind: VarDeclKind::Const,
+                                span: DUMMY_SP,
+                                ctxt: SyntaxContext::empty(),
+                            })))));
+                            // Assign debug label
+                            out.push(T::from(Stmt::Expr(ExprStmt {
+                                span: DUMMY_SP,
+                                expr: Box::new(create_assign_expr(
+                                    Expr::Ident(atom_ident.clone()),
+                                    "debugLabel".into(),
+                                    Expr::Lit(Lit::Str(label.into())),
+                                )),
+                            })));
+                            for (property, value) in debug_props {
+                                out.push(T::from(Stmt::Expr(ExprStmt {
+                                    span: DUMMY_SP,
+                                    expr: Box::new(create_assign_expr(
+                                        Expr::Ident(atom_ident.clone()),
+                                        property,
+                                        value,
+                                    )),
+                                })));
+                            }
+                            // Assign debug source
+                            if let Some(debug_source_expr) = self.create_debug_source_assign_expr(
+                                atom_ident.to_id(),
+                                &[],
+                                atom_span,
+                            ) {
+                                out.push(T::from(Stmt::Expr(ExprStmt {
+                                    span: DUMMY_SP,
+                                    expr: Box::new(debug_source_expr),
+                                })));
+                            }
+                            // export default expression
+                            out.push(
+                                <T as ModuleItemLike>::try_from_module_decl(
+                                    ModuleDecl::ExportDefaultExpr(ExportDefaultExpr {
+                                        expr: Box::new(Expr::Ident(atom_ident)),
+                                        span: DUMMY_SP,
+                                    }),
+                                )
+                                .unwrap(),
+                            );
+                            self.debug_label_exprs = outer_debug_label_exprs;
+                            return;
+                        }
+                        _ => {
+                            self.visit_mut_annotated(&mut module_decl);
+                            <T as ModuleItemLike>::try_from_module_decl(module_decl).unwrap()
+                        }
+                    }
+                }
+                Err(..) => unreachable!(),
+            },
+        };
+        out.push(stmt);
+
+        let debug_label_exprs = mem::replace(&mut self.debug_label_exprs, outer_debug_label_exprs);
+        out.extend(debug_label_exprs.into_iter().map(|expr| {
+            T::from(Stmt::Expr(ExprStmt {
+                span: DUMMY_SP,
+                expr: Box::new(expr),
+            }))
+        }));
+    }
+}
+
+impl VisitMut for DebugLabelTransformVisitor {
+    noop_visit_mut_type!();
+
+    fn visit_mut_module(&mut self, module: &mut Module) {
+        // A shared map is filled in by its owner.
+        if let Some(atom_import_map) = Rc::get_mut(&mut self.atom_import_map) {
+            atom_import_map.visit_module(module);
+        }
+        if is_disabled(self.comments.as_deref(), module.span, &module.body) {
+            return;
+        }
+        self.collect_debug_label_assignments(&*module);
+        self.collect_exports(&*module);
+        module.visit_mut_children_with(self);
+        self.strip_debug_labels(module);
+        self.insert_family_param_serializer(&mut module.body);
+    }
+
+    fn visit_mut_script(&mut self, script: &mut Script) {
+        if is_disabled(self.comments.as_deref(), script.span, &script.body) {
+            return;
+        }
+        self.collect_debug_label_assignments(&*script);
+        script.visit_mut_children_with(self);
+        self.strip_debug_labels(script);
+        self.insert_family_param_serializer(&mut script.body);
+    }
+
+    fn visit_mut_var_declarator(&mut self, var_declarator: &mut VarDeclarator) {
+        let old_var_declarator = self.current_var_declarator.take();
+
+        self.current_var_declarator = if let Pat::Ident(id) = &var_declarator.name {
+            Some(id.to_id())
+        } else {
+            None
+        };
+
+        let old_access_path = mem::take(&mut self.access_path);
+        let old_function_name = self.current_function_name.clone();
+        // The label of a directive only applies to the first declarator, as
+        // in `/* @jotai-label "Count" */ const countAtom = atom(0), b = ...`.
+        let old_label = mem::replace(&mut self.current_label, self.annotations.label.take());
+
+        // Functions assigned to a variable are named after it, e.g.
+        // `const Counter = () => {}`.
+        if let (Some(id), Some(Expr::Arrow(..) | Expr::Fn(..))) =
+            (&self.current_var_declarator, var_declarator.init.as_deref())
+        {
+            self.current_function_name = Some(id.0.clone());
+        }
+
+        var_declarator.name.visit_mut_with(self);
+        if let Some(init) = &mut var_declarator.init {
+            self.visit_mut_atom_path(init);
+        }
+
+        self.current_var_declarator = old_var_declarator;
+        self.access_path = old_access_path;
+        self.current_function_name = old_function_name;
+        self.current_label = old_label;
+    }
+
+    // Atoms returned from a function don't belong to the variable the function
+    // is assigned to, e.g. `const persisted = (init) => atomWithStorage(key, init)`.
+    fn visit_mut_function(&mut self, function: &mut Function) {
+        let old_var_declarator = self.current_var_declarator.take();
+        function.visit_mut_children_with(self);
+        self.current_var_declarator = old_var_declarator;
+    }
+
+    fn visit_mut_arrow_expr(&mut self, arrow: &mut ArrowExpr) {
+        let old_var_declarator = self.current_var_declarator.take();
+        arrow.visit_mut_children_with(self);
+        self.current_var_declarator = old_var_declarator;
+    }
+
+    fn visit_mut_fn_decl(&mut self, fn_decl: &mut FnDecl) {
+        let old_function_name = self
+            .current_function_name
+            .replace(fn_decl.ident.sym.clone());
+        fn_decl.visit_mut_children_with(self);
+        self.current_function_name = old_function_name;
+    }
+
+    fn visit_mut_fn_expr(&mut self, fn_expr: &mut FnExpr) {
+        let old_function_name = self.current_function_name.clone();
+        if let Some(ident) = &fn_expr.ident {
+            self.current_function_name = Some(ident.sym.clone());
+        }
+        fn_expr.visit_mut_children_with(self);
+        self.current_function_name = old_function_name;
+    }
+
+    fn visit_mut_class_decl(&mut self, class_decl: &mut ClassDecl) {
+        let class_path = Some((class_decl.ident.to_id(), Vec::new()));
+        self.visit_mut_class_with_path(&mut class_decl.class, class_path);
+    }
+
+    // The static members of a class passed to a function, as in
+    // `withLogging(class { ... })`, may not be reachable from the result.
+    fn visit_mut_class_expr(&mut self, class_expr: &mut ClassExpr) {
+        self.visit_mut_class_with_path(&mut class_expr.class, None);
+    }
+
+    fn visit_mut_export_default_decl(&mut self, export_default: &mut ExportDefaultDecl) {
+        // Handles: export default class CartStore { ... }
+        if let DefaultDecl::Class(ClassExpr {
+            ident: Some(ident),
+            class,
+        }) = &mut export_default.decl
+        {
+            let class_path = Some((ident.to_id(), Vec::new()));
+            self.visit_mut_class_with_path(class, class_path);
+            return;
+        }
+        export_default.visit_mut_children_with(self);
+    }
+
+    // Handles: static items = atom([]) and totalAtom = atom(0)
+    fn visit_mut_class_prop(&mut self, class_prop: &mut ClassProp) {
+        class_prop.key.visit_mut_with(self);
+        let Some(value) = &mut class_prop.value else {
+            return;
+        };
+        match prop_name_to_member_prop(&class_prop.key) {
+            Some(prop) => {
+                self.visit_mut_class_member(class_prop.span, prop, value, class_prop.is_static)
+            }
+            None => self.visit_mut_without_declarator(value),
+        }
+    }
+
+    // Handles: static #items = atom([])
+    fn visit_mut_private_prop(&mut self, private_prop: &mut PrivateProp) {
+        let Some(value) = &mut private_prop.value else {
+            return;
+        };
+        let prop = MemberProp::PrivateName(private_prop.key.clone());
+        self.visit_mut_class_member(private_prop.span, prop, value, false);
+    }
+
+    fn visit_mut_static_block(&mut self, static_block: &mut StaticBlock) {
+        let mut stmts: Vec<Stmt> = Vec::with_capacity(static_block.body.stmts.len());
+        for mut stmt in static_block.body.stmts.take() {
+            // Handles: static { this.items = atom([]) }
+            // The atoms are labeled after the class, like static properties.
+            let span = stmt.span();
+            if let Some((prop, value)) = this_assign_mut(&mut stmt) {
+                self.visit_mut_class_member(span, prop, value, true);
+                stmts.push(stmt);
+            } else {
+                self.transform_stmt_like(stmt, &mut stmts);
+            }
+        }
+        static_block.body.stmts = stmts;
+    }
+
+    fn visit_mut_call_expr(&mut self, call_expr: &mut CallExpr) {
+        // Handles: Object.assign(atom(0), { debugLabel: "count" })
+        if !self.overwrite_debug_labels && is_object_assign_with_debug_label(call_expr) {
+            let old_var_declarator = self.current_var_declarator.take();
+            call_expr.visit_mut_children_with(self);
+            self.current_var_declarator = old_var_declarator;
+            return;
+        }
+
+        // Atoms can be declared in callbacks, e.g. `useMemo(() => { ... })`.
+        // Atoms in the arguments aren't bound to the declarator, though.
+        let atom_name = self.current_var_declarator.take();
+        call_expr.visit_mut_children_with(self);
+        self.current_var_declarator = atom_name;
+
+        if self.mode != DebugLabelMode::Strip && !self.annotations.ignore {
+            if let Some(atom_name) = &self.current_var_declarator {
+                let family_label = self.create_debug_label(atom_name);
+                self.label_family_members(call_expr, &family_label);
+            }
+        }
+
+        if self.mode == DebugLabelMode::Inline || self.annotations.ignore {
+            return;
+        }
+        let Some(atom_name) = self.current_var_declarator.clone() else {
+            return;
+        };
+        if let Callee::Expr(expr) = &call_expr.callee {
+            if self.atom_import_map.is_atom_import(expr) {
+                if self.mode == DebugLabelMode::Strip {
+                    self.stripped_atoms
+                        .push(atom_member_expr(atom_name, &self.access_path));
+                    return;
+                }
+                let debug_label_expr = create_debug_label_assign_expr(
+                    atom_name.clone(),
+                    &self.access_path,
+                    Expr::Lit(Lit::Str(self.create_debug_label(&atom_name).into())),
+                );
+                // A declarator is labeled once, even if it creates several atoms.
+                if !self.debug_label_exprs.contains(&debug_label_expr)
+                    && !self
+                        .debug_label_assignments
+                        .contains(&atom_member_expr(atom_name.clone(), &self.access_path))
+                {
+                    self.debug_label_exprs.push(debug_label_expr);
+                    let debug_props =
+                        self.create_debug_props(&self.annotations, self.is_private(&atom_name));
+                    for (property, value) in debug_props {
+                        self.debug_label_exprs.push(create_assign_expr(
+                            atom_member_expr(atom_name.clone(), &self.access_path),
+                            property,
+                            value,
+                        ));
+                    }
+                    self.debug_label_exprs
+                        .extend(self.create_debug_source_assign_expr(
+                            atom_name,
+                            &self.access_path,
+                            call_expr.span,
+                        ));
+                }
+            }
+        }
+    }
+
+    fn visit_mut_for_stmt(&mut self, for_stmt: &mut ForStmt) {
+        self.visit_mut_loop_head(&mut for_stmt.init);
+        for_stmt.test.visit_mut_with(self);
+        for_stmt.update.visit_mut_with(self);
+        for_stmt.body.visit_mut_with(self);
+    }
+
+    fn visit_mut_for_in_stmt(&mut self, for_in: &mut ForInStmt) {
+        self.visit_mut_loop_head(&mut for_in.left);
+        for_in.right.visit_mut_with(self);
+        for_in.body.visit_mut_with(self);
+    }
+
+    fn visit_mut_for_of_stmt(&mut self, for_of: &mut ForOfStmt) {
+        self.visit_mut_loop_head(&mut for_of.left);
+        for_of.right.visit_mut_with(self);
+        for_of.body.visit_mut_with(self);
+    }
+
+    fn visit_mut_module_items(&mut self, items: &mut Vec<ModuleItem>) {
+        self.visit_mut_stmt_like(items);
+    }
+
+    fn visit_mut_stmts(&mut self, stmts: &mut Vec<Stmt>) {
+        self.visit_mut_stmt_like(stmts);
+    }
+}
+
+pub fn debug_label(config: Config, file_name: FileName) -> impl Pass {
+    visit_mut_pass(DebugLabelTransformVisitor::new(config, file_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use common::{AtomName, DebugLabelMode, DetectWrappers, JotaiVersion};
+    use swc_core::{
+        common::Mark,
+        ecma::{
+            parser::Syntax,
+            transforms::{
+                base::resolver,
+                testing::{test, test_inline},
+            },
+            visit::visit_mut_pass,
+        },
+    };
+
+    fn transform(config: Option<Config>, file_name: Option<FileName>) -> impl Pass {
+        (
+            resolver(Mark::new(), Mark::new(), false),
+            visit_mut_pass(DebugLabelTransformVisitor::new(
+                config.unwrap_or_default(),
+                file_name.unwrap_or(FileName::Real(PathBuf::from("atoms.ts"))),
+            )),
+        )
+    }
+
+    fn transform_with_source_map(config: Config, source_map: Lrc<SourceMapperDyn>) -> impl Pass {
+        (
+            resolver(Mark::new(), Mark::new(), false),
+            visit_mut_pass(
+                DebugLabelTransformVisitor::new(config, FileName::Real(PathBuf::from("atoms.ts")))
+                    .with_source_map(source_map),
+            ),
+        )
+    }
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(None, None),
+        basic,
+        r#"
+import { atom } from "jotai";
+const countAtom = atom(0);"#,
+        r#"
+import { atom } from "jotai";
+const countAtom = atom(0);
+countAtom.debugLabel = "countAtom";"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(None, None),
+        exported_atom,
+        r#"
+import { atom } from "jotai";
+export const countAtom = atom(0);"#,
+        r#"
+import { atom } from "jotai";
+export const countAtom = atom(0);
+countAtom.debugLabel = "countAtom";"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(None, None),
+        multiple_atoms,
+        r#"
+import { atom } from "jotai";
+const countAtom = atom(0);
+const doubleAtom = atom((get) => get(countAtom) * 2);"#,
+        r#"
+import { atom } from "jotai";
+const countAtom = atom(0);
+countAtom.debugLabel = "countAtom";
+const doubleAtom = atom((get) => get(countAtom) * 2);
+doubleAtom.debugLabel = "doubleAtom";"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(None, None),
+        multiple_atoms_between_code,
+        r#"
+import { atom } from "jotai";
+const countAtom = atom(0);
+let counter = 0;
+const increment = () => ++counter;
+const doubleAtom = atom((get) => get(countAtom) * 2);"#,
+        r#"
+import { atom } from "jotai";
+const countAtom = atom(0);
+countAtom.debugLabel = "countAtom";
+let counter = 0;
+const increment = () => ++counter;
+const doubleAtom = atom((get) => get(countAtom) * 2);
+doubleAtom.debugLabel = "doubleAtom";"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(None, None),
+        multiple_declarators,
+        r#"
+import { atom } from "jotai";
+const countAtom = atom(0), doubleAtom = atom((get) => get(countAtom) * 2);"#,
+        r#"
+import { atom } from "jotai";
+const countAtom = atom(0), doubleAtom = atom((get) => get(countAtom) * 2);
+countAtom.debugLabel = "countAtom";
+doubleAtom.debugLabel = "doubleAtom";"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(None, None),
+        multiple_declarators_with_nested_atoms,
+        r#"
+import { atom } from "jotai";
+const countAtom = atom(0), makeAtom = () => {
+  const innerAtom = atom(1);
+  return innerAtom;
+}, textAtom = atom("");"#,
+        r#"
+import { atom } from "jotai";
+const countAtom = atom(0), makeAtom = () => {
+  const innerAtom = atom(1);
+  innerAtom.debugLabel = "innerAtom";
+  return innerAtom;
+}, textAtom = atom("");
+countAtom.debugLabel = "countAtom";
+textAtom.debugLabel = "textAtom";"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(None, None),
+        atoms_in_object_literal,
+        r#"
+import { atom } from "jotai";
+const atoms = {
+  count: atom(0),
+  user: { name: atom(""), "last-name": atom("") },
+  other: "value",
+};"#,
+        r#"
+import { atom } from "jotai";
+const atoms = {
+  count: atom(0),
+  user: { name: atom(""), "last-name": atom("") },
+  other: "value",
+};
+atoms.count.debugLabel = "atoms.count";
+atoms.user.name.debugLabel = "atoms.user.name";
+atoms.user["last-name"].debugLabel = "atoms.user.last-name";"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(None, None),
+        atoms_in_array_literal,
+        r#"
+import { atom } from "jotai";
+const list = [atom(0), , { item: atom(1) }, [atom(2)]];"#,
+        r#"
+import { atom } from "jotai";
+const list = [atom(0), , { item: atom(1) }, [atom(2)]];
+list[0].debugLabel = "list.0";
+list[2].item.debugLabel = "list.2.item";
+list[3][0].debugLabel = "list.3.0";"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(None, None),
+        unreachable_atoms_in_literals,
+        r#"
+import { atom } from "jotai";
+const key = "count";
+const atoms = { [key]: atom(0), ...{ spread: atom(1) } };
+const list = [...[], atom(2)];"#,
+        r#"
+import { atom } from "jotai";
+const key = "count";
+const atoms = { [key]: atom(0), ...{ spread: atom(1) } };
+const list = [...[], atom(2)];"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(None, None),
+        atoms_in_callbacks,
+        r#"
+import { atom } from "jotai";
+import { useMemo } from "react";
+useEffect(() => {
+  const effectAtom = atom(0);
+});
+(() => {
+  const iifeAtom = atom(1);
+})();
+function Counter() {
+  const localAtom = useMemo(() => {
+    const memoAtom = atom(2);
+    return memoAtom;
+  }, []);
+}"#,
+        r#"
+import { atom } from "jotai";
+import { useMemo } from "react";
+useEffect(() => {
+  const effectAtom = atom(0);
+  effectAtom.debugLabel = "effectAtom";
+});
+(() => {
+  const iifeAtom = atom(1);
+  iifeAtom.debugLabel = "iifeAtom";
+})();
+function Counter() {
+  const localAtom = useMemo(() => {
+    const memoAtom = atom(2);
+    memoAtom.debugLabel = "memoAtom";
+    return memoAtom;
+  }, []);
+}"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(
+            Some(Config {
+                function_name_prefix: true,
+                ..Default::default()
+            }),
+            None
+        ),
+        function_name_prefix,
+        r#"
+import { atom } from "jotai";
+const topAtom = atom(0);
+function Counter() {
+  const localAtom = atom(1);
+  useEffect(() => {
+    const effectAtom = atom(2);
+  });
+}
+const useCounter = () => {
+  const hookAtom = atom(3);
+};
+const named = function inner() {
+  const innerAtom = atom(4);
+};"#,
+        r#"
+import { atom } from "jotai";
+const topAtom = atom(0);
+topAtom.debugLabel = "topAtom";
+function Counter() {
+  const localAtom = atom(1);
+  localAtom.debugLabel = "Counter.localAtom";
+  useEffect(() => {
+    const effectAtom = atom(2);
+    effectAtom.debugLabel = "Counter.effectAtom";
+  });
+}
+const useCounter = () => {
+  const hookAtom = atom(3);
+  hookAtom.debugLabel = "useCounter.hookAtom";
+};
+const named = function inner() {
+  const innerAtom = atom(4);
+  innerAtom.debugLabel = "inner.innerAtom";
+};"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(None, None),
+        import_alias,
+        r#"
+import { atom as blah } from "jotai";
+const countAtom = blah(0);"#,
+        r#"
+import { atom as blah } from "jotai";
+const countAtom = blah(0);
+countAtom.debugLabel = "countAtom";"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(None, None),
+        ignore_non_jotai_imports,
+        r#"
+import React from "react";
+import { atom } from "jotai";
+import { defaultCount } from "./utils";
+const countAtom = atom(0);"#,
+        r#"
+import React from "react";
+import { atom } from "jotai";
+import { defaultCount } from "./utils";      
+const countAtom = atom(0);
+countAtom.debugLabel = "countAtom";"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(None, None),
+        namespace_import,
+        r#"
+import * as jotai from "jotai";
+const countAtom = jotai.atom(0);"#,
+        r#"
+import * as jotai from "jotai";
+const countAtom = jotai.atom(0);
+countAtom.debugLabel = "countAtom";"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(None, None),
+        namespace_import_alias,
+        r#"
+import * as J from "jotai";
+import * as utils from "jotai/utils";
+const countAtom = J.atom(0);
+const storedAtom = utils.atomWithStorage("count", 0);"#,
+        r#"
+import * as J from "jotai";
+import * as utils from "jotai/utils";
+const countAtom = J.atom(0);
+countAtom.debugLabel = "countAtom";
+const storedAtom = utils.atomWithStorage("count", 0);
+storedAtom.debugLabel = "storedAtom";"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(None, None),
+        namespace_import_named_like_module,
+        r#"
+import * as J from "jotai";
+import * as jotai from "some-library";
+const countAtom = jotai.atom(0);"#,
+        r#"
+import * as J from "jotai";
+import * as jotai from "some-library";
+const countAtom = jotai.atom(0);"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(
+            Some(Config {
+                import_sources: vec!["jotai".into(), "@acme/state".into()],
+                ..Default::default()
+            }),
+            None
+        ),
+        nested_namespace_import,
+        r#"
+import * as state from "@acme/state";
+import { utils } from "@acme/state";
+const countAtom = state.utils.atomWithReset(0);
+const storedAtom = utils.atomWithStorage("count", 0);"#,
+        r#"
+import * as state from "@acme/state";
+import { utils } from "@acme/state";
+const countAtom = state.utils.atomWithReset(0);
+countAtom.debugLabel = "countAtom";
+const storedAtom = utils.atomWithStorage("count", 0);
+storedAtom.debugLabel = "storedAtom";"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(None, None),
+        named_import_is_not_namespace,
+        r#"
+import { useAtom, Provider } from "jotai";
+const countAtom = useAtom.atom(0);
+const storedAtom = Provider.utils.atomWithStorage("count", 0);"#,
+        r#"
+import { useAtom, Provider } from "jotai";
+const countAtom = useAtom.atom(0);
+const storedAtom = Provider.utils.atomWithStorage("count", 0);"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(None, None),
+        atom_from_another_package,
+        r#"
+import { atom } from "some-library";
+const countAtom = atom(0);"#,
+        r#"
+import { atom } from "some-library";
+const countAtom = atom(0);"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(None, None),
+        atom_from_jotai_prefixed_package,
+        r#"
+import { atomWithStorage } from "jotai-devtools";
+const countAtom = atomWithStorage("count", 0);"#,
+        r#"
+import { atomWithStorage } from "jotai-devtools";
+const countAtom = atomWithStorage("count", 0);"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(
+            Some(Config {
+                import_sources: vec!["@acme/*".into()],
+                ..Default::default()
+            }),
+            None
+        ),
+        custom_import_sources,
+        r#"
+import { atom } from "@acme/state";
+import { atomWithStorage } from "jotai/utils";
+const countAtom = atom(0);
+const storedAtom = atomWithStorage("count", 0);"#,
+        r#"
+import { atom } from "@acme/state";
+import { atomWithStorage } from "jotai/utils";
+const countAtom = atom(0);
+countAtom.debugLabel = "countAtom";
+const storedAtom = atomWithStorage("count", 0);"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(None, None),
+        no_jotai_import,
+        "const countAtom = atom(0);",
+        "const countAtom = atom(0);"
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(None, None),
+        handle_default_export,
+        r#"
+import { atom } from "jotai";
+export default atom(0);"#,
+        r#"
+import { atom } from "jotai";
+const atoms = atom(0);
+atoms.debugLabel = "atoms";
+export default atoms;"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(None, Some(FileName::Real("countAtom.ts".parse().unwrap()))),
+        handle_file_naming_default_export,
+        r#"
+import { atom } from "jotai";
+export default atom(0);"#,
+        r#"
+import { atom } from "jotai";
+const countAtom = atom(0);
+countAtom.debugLabel = "countAtom";
+export default countAtom;"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(
+            None,
+            Some(FileName::Real("src/atoms/countAtom.ts".parse().unwrap()))
+        ),
+        handle_file_path_default_export,
+        r#"
+import { atom } from "jotai";
+export default atom(0);"#,
+        r#"
+import { atom } from "jotai";
+const countAtom = atom(0);
+countAtom.debugLabel = "countAtom";
+export default countAtom;"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(
+            None,
+            Some(FileName::Real("src/counter/index.ts".parse().unwrap()))
+        ),
+        default_export_from_index_file,
+        r#"
+import { atom } from "jotai";
+export default atom(0);"#,
+        r#"
+import { atom } from "jotai";
+const counter = atom(0);
+counter.debugLabel = "counter";
+export default counter;"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(None, Some(FileName::Real("my-atoms.ts".parse().unwrap()))),
+        default_export_sanitized_name,
+        r#"
+import { atom } from "jotai";
+export default atom(0);"#,
+        r#"
+import { atom } from "jotai";
+const myAtoms = atom(0);
+myAtoms.debugLabel = "my-atoms";
+export default myAtoms;"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(None, Some(FileName::Real("404.ts".parse().unwrap()))),
+        default_export_numeric_name,
+        r#"
+import { atom } from "jotai";
+export default atom(0);"#,
+        r#"
+import { atom } from "jotai";
+const _404 = atom(0);
+_404.debugLabel = "404";
+export default _404;"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(None, Some(FileName::Real("default.ts".parse().unwrap()))),
+        default_export_reserved_name,
+        r#"
+import { atom } from "jotai";
+export default atom(0);"#,
+        r#"
+import { atom } from "jotai";
+const _default = atom(0);
+_default.debugLabel = "default";
+export default _default;"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(None, Some(FileName::Real("countAtom.ts".parse().unwrap()))),
+        default_export_name_collision,
+        r#"
+import { atom } from "jotai";
+const countAtom = atom(0);
+export default atom((get) => get(countAtom) * 2);"#,
+        r#"
+import { atom } from "jotai";
+const countAtom = atom(0);
+countAtom.debugLabel = "countAtom";
+const countAtom1 = atom((get) => get(countAtom) * 2);
+countAtom1.debugLabel = "countAtom";
+export default countAtom1;"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(None, Some(FileName::Anon)),
+        default_export_anonymous_file,
+        r#"
+import { atom } from "jotai";
+export default atom(0);"#,
+        r#"
+import { atom } from "jotai";
+const default_atom = atom(0);
+default_atom.debugLabel = "default_atom";
+export default default_atom;"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(
+            Some(Config {
+                label_template: "[dir]/[file]:[name]".into(),
+                root: Some("/repo/src".into()),
+                ..Default::default()
+            }),
+            Some(FileName::Real("/repo/src/state/count.ts".into()))
+        ),
+        label_template,
+        r#"
+import { atom } from "jotai";
+const countAtom = atom(0);
+const atoms = { double: atom(0) };
+export default atom(0);"#,
+        r#"
+import { atom } from "jotai";
+const countAtom = atom(0);
+countAtom.debugLabel = "state/count:countAtom";
+const atoms = { double: atom(0) };
+atoms.double.debugLabel = "state/count:atoms.double";
+const count = atom(0);
+count.debugLabel = "state/count:count";
+export default count;"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(
+            Some(Config {
+                label_template: "[dir]/[file]:[name]".into(),
+                root: Some("/repo".into()),
+                ..Default::default()
+            }),
+            Some(FileName::Real("/repo/app/[name]/page.tsx".into()))
+        ),
+        label_template_with_brackets_in_path,
+        r#"
+import { atom } from "jotai";
+const countAtom = atom(0);"#,
+        r#"
+import { atom } from "jotai";
+const countAtom = atom(0);
+countAtom.debugLabel = "app/[name]/page:countAtom";"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(
+            Some(Config {
+                label_template: "[file]:[name]".into(),
+                ..Default::default()
+            }),
+            Some(FileName::Anon)
+        ),
+        label_template_without_file_name,
+        r#"
+import { atom } from "jotai";
+const countAtom = atom(0);"#,
+        r#"
+import { atom } from "jotai";
+const countAtom = atom(0);
+countAtom.debugLabel = "countAtom";"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |t| transform_with_source_map(
+            Config {
+                debug_source: true,
+                ..Default::default()
+            },
+            t.cm.clone()
+        ),
+        debug_source,
+        r#"
+import { atom } from "jotai";
+const countAtom = atom(0);
+const atoms = {
+  text: atom(""),
+};
+export default atom(1);"#,
+        r#"
+import { atom } from "jotai";
+const countAtom = atom(0);
+countAtom.debugLabel = "countAtom";
+countAtom.debugSource = { fileName: "atoms.ts", line: 3, column: 19 };
+const atoms = {
+  text: atom(""),
+};
+atoms.text.debugLabel = "atoms.text";
+atoms.text.debugSource = { fileName: "atoms.ts", line: 5, column: 9 };
+const atoms1 = atom(1);
+atoms1.debugLabel = "atoms";
+atoms1.debugSource = { fileName: "atoms.ts", line: 7, column: 16 };
+export default atoms1;"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |t| transform_with_source_map(
+            Config {
+                debug_source: true,
+                debug_source_property: "__source".into(),
+                ..Default::default()
+            },
+            t.cm.clone()
+        ),
+        debug_source_property,
+        r#"
+import { atom } from "jotai";
+const countAtom = atom(0);"#,
+        r#"
+import { atom } from "jotai";
+const countAtom = atom(0);
+countAtom.debugLabel = "countAtom";
+countAtom.__source = { fileName: "atoms.ts", line: 3, column: 19 };"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |t| transform_with_source_map(
+            Config {
+                mode: DebugLabelMode::Inline,
+                debug_source: true,
+                ..Default::default()
+            },
+            t.cm.clone()
+        ),
+        inline_debug_source,
+        r#"
+import { atom } from "jotai";
+const countAtom = atom(0);"#,
+        r#"
+import { atom } from "jotai";
+const countAtom = Object.assign(atom(0), {
+  debugLabel: "countAtom",
+  debugSource: { fileName: "atoms.ts", line: 3, column: 19 },
+});"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(
+            Some(Config {
+                debug_source: true,
+                ..Default::default()
+            }),
+            None
+        ),
+        debug_source_without_source_map,
+        r#"
+import { atom } from "jotai";
+const countAtom = atom(0);"#,
+        r#"
+import { atom } from "jotai";
+const countAtom = atom(0);
+countAtom.debugLabel = "countAtom";"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(None, None),
+        jotai_utils_import,
+        r#"
+import { atomWithImmer } from "jotai/immer";
+import { atomWithMachine } from "jotai/xstate";
+const immerAtom = atomWithImmer(0);
+const toggleMachineAtom = atomWithMachine(() => toggleMachine);"#,
+        r#"
+import { atomWithImmer } from "jotai/immer";
+import { atomWithMachine } from "jotai/xstate";
+const immerAtom = atomWithImmer(0);
+immerAtom.debugLabel = "immerAtom";
+const toggleMachineAtom = atomWithMachine(() => toggleMachine);
+toggleMachineAtom.debugLabel = "toggleMachineAtom";"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(None, None),
+        jotai_ecosystem_import,
+        r#"
+import { unwrap, atomWithRefresh, atomWithLazy } from "jotai/utils";
+import { atomEffect } from "jotai-effect";
+import { atomWithQuery } from "jotai-tanstack-query";
+const asyncAtom = unwrap(promiseAtom);
+const refreshAtom = atomWithRefresh(fetchUser);
+const lazyAtom = atomWithLazy(init);
+const logEffect = atomEffect((get) => console.log(get(asyncAtom)));
+const userAtom = atomWithQuery(() => ({ queryKey: ["user"] }));"#,
+        r#"
+import { unwrap, atomWithRefresh, atomWithLazy } from "jotai/utils";
+import { atomEffect } from "jotai-effect";
+import { atomWithQuery } from "jotai-tanstack-query";
+const asyncAtom = unwrap(promiseAtom);
+asyncAtom.debugLabel = "asyncAtom";
+const refreshAtom = atomWithRefresh(fetchUser);
+refreshAtom.debugLabel = "refreshAtom";
+const lazyAtom = atomWithLazy(init);
+lazyAtom.debugLabel = "lazyAtom";
+const logEffect = atomEffect((get) => console.log(get(asyncAtom)));
+logEffect.debugLabel = "logEffect";
+const userAtom = atomWithQuery(() => ({ queryKey: ["user"] }));
+userAtom.debugLabel = "userAtom";"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(None, None),
+        factory_from_wrong_package,
+        r#"
+import { atomWithMachine } from "jotai-immer";
+import * as utils from "jotai/utils";
+const machineAtom = atomWithMachine(() => machine);
+const immerAtom = utils.atomWithImmer(0);"#,
+        r#"
+import { atomWithMachine } from "jotai-immer";
+import * as utils from "jotai/utils";
+const machineAtom = atomWithMachine(() => machine);
+const immerAtom = utils.atomWithImmer(0);"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(
+            Some(Config {
+                jotai_version: Some(JotaiVersion::V2),
+                ..Default::default()
+            }),
+            None
+        ),
+        jotai_version,
+        r#"
+import { atomWithImmer } from "jotai/immer";
+import { atomWithImmer as withImmer } from "jotai-immer";
+const legacyAtom = atomWithImmer(0);
+const immerAtom = withImmer(0);"#,
+        r#"
+import { atomWithImmer } from "jotai/immer";
+import { atomWithImmer as withImmer } from "jotai-immer";
+const legacyAtom = atomWithImmer(0);
+const immerAtom = withImmer(0);
+immerAtom.debugLabel = "immerAtom";"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(
+            Some(Config {
+                atom_factories: [("@acme/state".into(), vec!["createAtom".into()])].into(),
+                ..Default::default()
+            }),
+            None
+        ),
+        custom_atom_factories,
+        r#"
+import { createAtom, createStore } from "@acme/state";
+const countAtom = createAtom(0);
+const store = createStore();"#,
+        r#"
+import { createAtom, createStore } from "@acme/state";
+const countAtom = createAtom(0);
+countAtom.debugLabel = "countAtom";
+const store = createStore();"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(None, None),
+        test_default_export,
+        r#"
+function fn() { return true; }
+        
+export default fn;"#,
+        r#"
+function fn() { return true; }
+                
+export default fn;"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(None, None),
+        basic_with_existing_debug_label,
+        r#"
+import { atom } from "jotai";
+const countAtom = atom(0);
+countAtom.debugLabel = "fancyAtomName";"#,
+        r#"
+import { atom } from "jotai";
+const countAtom = atom(0);
+countAtom.debugLabel = "fancyAtomName";"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(
+            Some(Config {
+                overwrite_debug_labels: true,
+                ..Default::default()
+            }),
+            None
+        ),
+        overwrite_existing_debug_label,
+        r#"
+import { atom } from "jotai";
+const countAtom = atom(0);
+countAtom.debugLabel = "fancyAtomName";"#,
+        r#"
+import { atom } from "jotai";
+const countAtom = atom(0);
+countAtom.debugLabel = "countAtom";
+countAtom.debugLabel = "fancyAtomName";"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(None, None),
+        existing_debug_label_in_function,
+        r#"
+import { atom } from "jotai";
+export const countAtom = atom(0);
+export const doubleAtom = atom(0);
+function setup() {
+  countAtom.debugLabel = "count";
+}"#,
+        r#"
+import { atom } from "jotai";
+export const countAtom = atom(0);
+export const doubleAtom = atom(0);
+doubleAtom.debugLabel = "doubleAtom";
+function setup() {
+  countAtom.debugLabel = "count";
+}"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(None, None),
+        existing_debug_label_in_object,
+        r#"
+import { atom } from "jotai";
+const atoms = {
+  count: atom(0),
+  text: atom(""),
+};
+atoms.count.debugLabel = "count";"#,
+        r#"
+import { atom } from "jotai";
+const atoms = {
+  count: atom(0),
+  text: atom(""),
+};
+atoms.text.debugLabel = "atoms.text";
+atoms.count.debugLabel = "count";"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(None, None),
+        existing_debug_label_object_assign,
+        r#"
+import { atom } from "jotai";
+const countAtom = Object.assign(atom(0), { debugLabel: "count" });
+const debugLabel = "text";
+const textAtom = Object.assign(atom(""), { debugLabel });"#,
+        r#"
+import { atom } from "jotai";
+const countAtom = Object.assign(atom(0), { debugLabel: "count" });
+const debugLabel = "text";
+const textAtom = Object.assign(atom(""), { debugLabel });"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(
+            Some(Config {
+                mode: DebugLabelMode::Inline,
+                ..Default::default()
+            }),
+            None
+        ),
+        inline_existing_debug_label,
+        r#"
+import { atom } from "jotai";
+const countAtom = atom(0);
+const textAtom = atom("");
+countAtom.debugLabel = "count";"#,
+        r#"
+import { atom } from "jotai";
+const countAtom = atom(0);
+const textAtom = Object.assign(atom(""), { debugLabel: "textAtom" });
+countAtom.debugLabel = "count";"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(
+            Some(Config {
+                mode: DebugLabelMode::Inline,
+                function_name_prefix: true,
+                ..Default::default()
+            }),
+            None
+        ),
+        inline_function_name_prefix,
+        r#"
+import { atom } from "jotai";
+function useCounter() {
+  const countAtom = useMemo(() => atom(0), []);
+  const textAtom = atom("");
+}"#,
+        r#"
+import { atom } from "jotai";
+function useCounter() {
+  const countAtom = useMemo(() => atom(0), []);
+  const textAtom = Object.assign(atom(""), { debugLabel: "useCounter.textAtom" });
+}"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(
+            Some(Config {
+                mode: DebugLabelMode::Inline,
+                private_unexported: true,
+                ..Default::default()
+            }),
+            None
+        ),
+        inline_private_unexported,
+        r#"
+import { atom } from "jotai";
+const baseAtom = atom(0);
+export const countAtom = atom((get) => get(baseAtom));"#,
+        r#"
+import { atom } from "jotai";
+const baseAtom = Object.assign(atom(0), { debugLabel: "baseAtom", debugPrivate: true });
+export const countAtom = Object.assign(atom((get) => get(baseAtom)), {
+  debugLabel: "countAtom",
+});"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(
+            Some(Config {
+                private_atom_names: vec!["_*".into()],
+                ..Default::default()
+            }),
+            None
+        ),
+        private_atom_names,
+        r#"
+import { atom } from "jotai";
+const _baseAtom = atom(0);
+const atoms = {
+  _hidden: atom(0),
+};
+export default _baseAtom;"#,
+        r#"
+import { atom } from "jotai";
+const _baseAtom = atom(0);
+_baseAtom.debugLabel = "_baseAtom";
+_baseAtom.debugPrivate = true;
+const atoms = {
+  _hidden: atom(0),
+};
+atoms._hidden.debugLabel = "atoms._hidden";
+export default _baseAtom;"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(
+            Some(Config {
+                atom_names: vec!["customAtom".into()],
+                ..Default::default()
+            }),
+            None
+        ),
+        custom_atom_names,
+        r#"
+const myCustomAtom = customAtom(0);"#,
+        r#"
+const myCustomAtom = customAtom(0);
+myCustomAtom.debugLabel = "myCustomAtom";"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(
+            Some(Config {
+                atom_names: vec![
+                    AtomName::Import {
+                        name: "createAtom".into(),
+                        from: "@/lib/state".into()
+                    },
+                    AtomName::Import {
+                        name: "default".into(),
+                        from: "@/lib/persisted".into()
+                    },
+                ],
+                ..Default::default()
+            }),
+            None
+        ),
+        custom_atom_names_from_module,
+        r#"
+import { createAtom as create } from "@/lib/state";
+import * as state from "@/lib/state";
+import persisted from "@/lib/persisted";
+const countAtom = create(0);
+const textAtom = state.createAtom("");
+const themeAtom = persisted("light");"#,
+        r#"
+import { createAtom as create } from "@/lib/state";
+import * as state from "@/lib/state";
+import persisted from "@/lib/persisted";
+const countAtom = create(0);
+countAtom.debugLabel = "countAtom";
+const textAtom = state.createAtom("");
+textAtom.debugLabel = "textAtom";
+const themeAtom = persisted("light");
+themeAtom.debugLabel = "themeAtom";"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(
+            Some(Config {
+                atom_names: vec![AtomName::Import {
+                    name: "createAtom".into(),
+                    from: "@/lib/state".into()
+                }],
+                ..Default::default()
+            }),
+            None
+        ),
+        custom_atom_names_from_other_module,
+        r#"
+import { createAtom } from "./state";
+const countAtom = createAtom(0);
+const textAtom = createText("");"#,
+        r#"
+import { createAtom } from "./state";
+const countAtom = createAtom(0);
+const textAtom = createText("");"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(
+            Some(Config {
+                detect_wrappers: DetectWrappers::Off,
+                ..Default::default()
+            }),
+            None
+        ),
+        detect_wrappers_off,
+        r#"
+import { atom } from "jotai";
+function boxed(value) {
+  return atom({ value });
+}
+const boxAtom = boxed(1);"#,
+        r#"
+import { atom } from "jotai";
+function boxed(value) {
+  return atom({ value });
+}
+const boxAtom = boxed(1);"#
+    );
+
+    test_inline!(
+        Syntax::default(),
+        |_| transform(None, Some(FileName::Anon)),
+        filename_anon,
+        r#"
+import { atom } from "jotai";
+const countAtom = atom(0);"#,
+        r#"
+import { atom } from "jotai";
+const countAtom = atom(0);
+countAtom.debugLabel = "countAtom";"#
+    );
+}
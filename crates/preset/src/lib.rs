@@ -0,0 +1,174 @@
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
+use std::{path::PathBuf, rc::Rc};
+
+use common::{is_disabled, parse_preset_config, AtomImportMap, PresetConfig};
+use swc_core::{
+    common::{
+        comments::Comments, errors::SourceMapperDyn, sync::Lrc, util::take::Take, FileName, Spanned,
+    },
+    ecma::{
+        ast::*,
+        utils::{ModuleItemLike, StmtLike},
+        visit::{noop_visit_mut_type, visit_mut_pass, VisitMut, VisitMutWith, VisitWith},
+    },
+    plugin::{
+        metadata::TransformPluginMetadataContextKind, plugin_transform,
+        proxies::TransformPluginProgramMetadata,
+    },
+};
+use swc_jotai_debug_label_visitor::{
+    DebugLabelAssignments, DebugLabelStripper, DebugLabelTransformVisitor, ExportedBindings,
+};
+use swc_jotai_react_refresh_visitor::ReactRefreshTransformVisitor;
+
+const PLUGIN_NAME: &str = "@swc-jotai/preset";
+
+/// Runs the debug label and React Refresh transforms as a single plugin. The
+/// imports are collected once, and the top-level items are labeled before
+/// their atoms are cached, so the output is the same as running the two
+/// plugins one after the other.
+pub struct PresetTransformVisitor {
+    config: PresetConfig,
+    file_name: FileName,
+    source_map: Option<Lrc<SourceMapperDyn>>,
+    comments: Option<Rc<dyn Comments>>,
+}
+
+impl PresetTransformVisitor {
+    /// Creates a visitor for `file_name`, with the shared config already
+    /// resolved for the file by [`Config::for_file`](common::Config::for_file).
+    pub fn new(config: PresetConfig, file_name: FileName) -> Self {
+        Self {
+            config,
+            file_name,
+            source_map: None,
+            comments: None,
+        }
+    }
+
+    /// Sets the source map `debugSource` locations are looked up in.
+    pub fn with_source_map(mut self, source_map: Lrc<SourceMapperDyn>) -> Self {
+        self.source_map = Some(source_map);
+        self
+    }
+
+    /// Sets the comments directives are read from, and inline labels are
+    /// annotated as `#__PURE__` in.
+    pub fn with_comments(mut self, comments: Rc<dyn Comments>) -> Self {
+        self.comments = Some(comments);
+        self
+    }
+
+    fn transform_stmt_likes<T>(&self, stmts: &mut Vec<T>, atom_import_map: AtomImportMap)
+    where
+        T: StmtLike
+            + ModuleItemLike
+            + Spanned
+            + VisitMutWith<DebugLabelTransformVisitor>
+            + VisitMutWith<ReactRefreshTransformVisitor>,
+        Vec<T>: VisitWith<DebugLabelAssignments>
+            + VisitWith<ExportedBindings>
+            + VisitMutWith<DebugLabelStripper>,
+    {
+        let atom_import_map = Rc::new(atom_import_map);
+        let mut debug_label = self.config.debug_label.then(|| {
+            let visitor = DebugLabelTransformVisitor::with_atom_import_map(
+                &self.config.config,
+                self.file_name.clone(),
+                atom_import_map.clone(),
+            );
+            let mut visitor = match &self.source_map {
+                Some(source_map) => visitor.with_source_map(source_map.clone()),
+                None => visitor,
+            };
+            if let Some(comments) = &self.comments {
+                visitor = visitor.with_comments(comments.clone());
+            }
+            visitor.collect_debug_label_assignments(&*stmts);
+            visitor.collect_exports(&*stmts);
+            visitor
+        });
+        let mut react_refresh = self.config.react_refresh.then(|| {
+            let visitor = ReactRefreshTransformVisitor::with_atom_import_map(
+                self.file_name.clone(),
+                atom_import_map.clone(),
+            );
+            match &self.comments {
+                Some(comments) => visitor.with_comments(comments.clone()),
+                None => visitor,
+            }
+        });
+
+        if let Some(debug_label) = &mut debug_label {
+            let mut stmts_updated: Vec<T> = Vec::with_capacity(stmts.len());
+            for stmt in stmts.take() {
+                debug_label.transform_stmt_like(stmt, &mut stmts_updated);
+            }
+            // Atoms are stripped before they are wrapped in the cache.
+            debug_label.strip_debug_labels(&mut stmts_updated);
+            debug_label.insert_family_param_serializer(&mut stmts_updated);
+            *stmts = stmts_updated;
+        }
+        if let Some(react_refresh) = &mut react_refresh {
+            for stmt in stmts.iter_mut() {
+                react_refresh.transform_stmt_like(stmt);
+            }
+            react_refresh.insert_atom_cache(stmts);
+        }
+    }
+}
+
+impl VisitMut for PresetTransformVisitor {
+    noop_visit_mut_type!();
+
+    fn visit_mut_module(&mut self, module: &mut Module) {
+        if is_disabled(self.comments.as_deref(), module.span, &module.body) {
+            return;
+        }
+        let mut atom_import_map = AtomImportMap::new(&self.config.config);
+        atom_import_map.visit_module(module);
+        self.transform_stmt_likes(&mut module.body, atom_import_map);
+    }
+
+    fn visit_mut_script(&mut self, script: &mut Script) {
+        if is_disabled(self.comments.as_deref(), script.span, &script.body) {
+            return;
+        }
+        let atom_import_map = AtomImportMap::new(&self.config.config);
+        self.transform_stmt_likes(&mut script.body, atom_import_map);
+    }
+}
+
+pub fn preset(config: PresetConfig, file_name: FileName) -> impl Pass {
+    visit_mut_pass(PresetTransformVisitor::new(config, file_name))
+}
+
+#[plugin_transform]
+pub fn preset_transform(program: Program, metadata: TransformPluginProgramMetadata) -> Program {
+    let mut preset_config = parse_preset_config(
+        PLUGIN_NAME,
+        &metadata
+            .get_transform_plugin_config()
+            .unwrap_or_else(|| panic!("Failed to get plugin config for {PLUGIN_NAME}")),
+    );
+    let file_name = match &metadata.get_context(&TransformPluginMetadataContextKind::Filename) {
+        Some(file_name) => FileName::Real(file_name.into()),
+        None => FileName::Anon,
+    };
+    let cwd = metadata
+        .get_context(&TransformPluginMetadataContextKind::Cwd)
+        .map(PathBuf::from);
+    let config = &mut preset_config.config;
+    *config = config.for_file(&file_name, cwd.as_deref());
+    config.resolve_root(cwd.as_deref());
+    if !config.is_file_included(&file_name, cwd.as_deref()) {
+        return program;
+    }
+    let mut visitor = PresetTransformVisitor::new(preset_config, file_name)
+        .with_source_map(Lrc::new(metadata.source_map));
+    if let Some(comments) = metadata.comments {
+        visitor = visitor.with_comments(Rc::new(comments));
+    }
+    program.apply(&mut visit_mut_pass(visitor))
+}
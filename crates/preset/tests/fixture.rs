@@ -0,0 +1,90 @@
+use std::{
+    fs::read_to_string,
+    path::{Path, PathBuf},
+};
+
+use common::{parse_preset_config, PresetConfig};
+use swc_core::{
+    common::{FileName, Mark},
+    ecma::parser::{EsSyntax, Syntax},
+    ecma::transforms::{base::resolver, testing::test_fixture},
+    ecma::visit::visit_mut_pass,
+};
+use swc_jotai_debug_label_visitor::DebugLabelTransformVisitor;
+use swc_jotai_preset::PresetTransformVisitor;
+use swc_jotai_react_refresh_visitor::ReactRefreshTransformVisitor;
+use testing::fixture;
+
+/// Resolves the config for the fixture file, like the plugin does.
+fn read_config(input: &Path) -> PresetConfig {
+    let config =
+        read_to_string(input.with_file_name("config.json")).expect("Failed to read config.json");
+    let mut config = parse_preset_config("@swc-jotai/preset", &config);
+    config.config = config.config.for_file(&file_name(), None);
+    config
+}
+
+fn syntax() -> Syntax {
+    Syntax::Es(EsSyntax {
+        jsx: true,
+        ..Default::default()
+    })
+}
+
+fn file_name() -> FileName {
+    FileName::Real("atoms.ts".parse().unwrap())
+}
+
+#[fixture("tests/fixtures/**/input.js")]
+fn test(input: PathBuf) {
+    let config = read_config(&input);
+    let output = input.with_file_name("output.js");
+
+    test_fixture(
+        syntax(),
+        &|t| {
+            (
+                resolver(Mark::new(), Mark::new(), false),
+                visit_mut_pass(
+                    PresetTransformVisitor::new(config.clone(), file_name())
+                        .with_comments(t.comments.clone()),
+                ),
+            )
+        },
+        &input,
+        &output,
+        Default::default(),
+    )
+}
+
+/// The preset must produce the same output as running the plugins one after
+/// the other.
+#[fixture("tests/fixtures/**/input.js")]
+fn test_sequential(input: PathBuf) {
+    let config = read_config(&input);
+    let output = input.with_file_name("output.js");
+
+    test_fixture(
+        syntax(),
+        &|t| {
+            (
+                resolver(Mark::new(), Mark::new(), false),
+                config.debug_label.then(|| {
+                    visit_mut_pass(
+                        DebugLabelTransformVisitor::new(config.config.clone(), file_name())
+                            .with_comments(t.comments.clone()),
+                    )
+                }),
+                config.react_refresh.then(|| {
+                    visit_mut_pass(
+                        ReactRefreshTransformVisitor::new(config.config.clone(), file_name())
+                            .with_comments(t.comments.clone()),
+                    )
+                }),
+            )
+        },
+        &input,
+        &output,
+        Default::default(),
+    )
+}
@@ -0,0 +1,27 @@
+import { atom } from "jotai";
+import { atomWithStorage } from "jotai/utils";
+
+function persisted(key, init) {
+  return atomWithStorage(key, init);
+}
+
+const boxed = function (value) {
+  return atom({ value });
+};
+
+const persistedCount = (key) => persisted(key, 0);
+
+export function exportedWrapper() {
+  return atom(0);
+}
+
+function notAWrapper() {
+  const inner = () => atom(0);
+  return inner;
+}
+
+const themeAtom = persisted("theme", "light");
+const boxAtom = boxed(1);
+const countAtom = persistedCount("count");
+const exportedAtom = exportedWrapper();
+const innerAtom = notAWrapper()();
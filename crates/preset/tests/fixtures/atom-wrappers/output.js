@@ -0,0 +1,36 @@
+globalThis.jotaiAtomCache = globalThis.jotaiAtomCache || {
+    cache: new Map(),
+    get (name, inst) {
+        if (this.cache.has(name)) {
+            return this.cache.get(name);
+        }
+        this.cache.set(name, inst);
+        return inst;
+    }
+};
+import { atom } from "jotai";
+import { atomWithStorage } from "jotai/utils";
+function persisted(key, init) {
+    return atomWithStorage(key, init);
+}
+const boxed = function(value) {
+    return atom({
+        value
+    });
+};
+const persistedCount = (key)=>persisted(key, 0);
+export function exportedWrapper() {
+    return atom(0);
+}
+function notAWrapper() {
+    const inner = ()=>atom(0);
+    return inner;
+}
+const themeAtom = globalThis.jotaiAtomCache.get("atoms.ts/themeAtom", persisted("theme", "light"));
+themeAtom.debugLabel = "themeAtom";
+const boxAtom = globalThis.jotaiAtomCache.get("atoms.ts/boxAtom", boxed(1));
+boxAtom.debugLabel = "boxAtom";
+const countAtom = globalThis.jotaiAtomCache.get("atoms.ts/countAtom", persistedCount("count"));
+countAtom.debugLabel = "countAtom";
+const exportedAtom = exportedWrapper();
+const innerAtom = notAWrapper()();
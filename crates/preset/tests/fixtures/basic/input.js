@@ -0,0 +1,16 @@
+import { atom } from "jotai";
+import * as utils from "jotai/utils";
+
+export const countAtom = atom(0);
+const doubleAtom = atom((get) => get(countAtom) * 2);
+const resettableAtom = utils.atomWithReset(0);
+
+const atoms = {
+  text: atom(""),
+  list: [atom(1), atom(2)],
+};
+
+function Counter() {
+  const localAtom = atom(0);
+  return localAtom;
+}
@@ -0,0 +1,33 @@
+globalThis.jotaiAtomCache = globalThis.jotaiAtomCache || {
+    cache: new Map(),
+    get (name, inst) {
+        if (this.cache.has(name)) {
+            return this.cache.get(name);
+        }
+        this.cache.set(name, inst);
+        return inst;
+    }
+};
+import { atom } from "jotai";
+import * as utils from "jotai/utils";
+export const countAtom = globalThis.jotaiAtomCache.get("atoms.ts/countAtom", atom(0));
+countAtom.debugLabel = "countAtom";
+const doubleAtom = globalThis.jotaiAtomCache.get("atoms.ts/doubleAtom", atom((get)=>get(countAtom) * 2));
+doubleAtom.debugLabel = "doubleAtom";
+const resettableAtom = globalThis.jotaiAtomCache.get("atoms.ts/resettableAtom", utils.atomWithReset(0));
+resettableAtom.debugLabel = "resettableAtom";
+const atoms = {
+    text: globalThis.jotaiAtomCache.get("atoms.ts/atoms.text", atom("")),
+    list: [
+        globalThis.jotaiAtomCache.get("atoms.ts/atoms.list.0", atom(1)),
+        globalThis.jotaiAtomCache.get("atoms.ts/atoms.list.1", atom(2))
+    ]
+};
+atoms.text.debugLabel = "atoms.text";
+atoms.list[0].debugLabel = "atoms.list.0";
+atoms.list[1].debugLabel = "atoms.list.1";
+function Counter() {
+    const localAtom = atom(0);
+    localAtom.debugLabel = "localAtom";
+    return localAtom;
+}
@@ -0,0 +1,10 @@
+import { atom } from "jotai";
+
+class CartStore {
+  static items = atom([]);
+  totalAtom = atom(0);
+
+  static {
+    this.countAtom = atom(0);
+  }
+}
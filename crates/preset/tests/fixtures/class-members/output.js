@@ -0,0 +1,22 @@
+globalThis.jotaiAtomCache = globalThis.jotaiAtomCache || {
+    cache: new Map(),
+    get (name, inst) {
+        if (this.cache.has(name)) {
+            return this.cache.get(name);
+        }
+        this.cache.set(name, inst);
+        return inst;
+    }
+};
+import { atom } from "jotai";
+class CartStore {
+    static items = globalThis.jotaiAtomCache.get("atoms.ts/CartStore.items", atom([]));
+    totalAtom = /*#__PURE__*/ Object.assign(atom(0), {
+        debugLabel: "CartStore.totalAtom"
+    });
+    static{
+        this.countAtom = globalThis.jotaiAtomCache.get("atoms.ts/CartStore.countAtom", atom(0));
+    }
+}
+CartStore.items.debugLabel = "CartStore.items";
+CartStore.countAtom.debugLabel = "CartStore.countAtom";
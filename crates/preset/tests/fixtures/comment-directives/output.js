@@ -0,0 +1,22 @@
+globalThis.jotaiAtomCache = globalThis.jotaiAtomCache || {
+    cache: new Map(),
+    get (name, inst) {
+        if (this.cache.has(name)) {
+            return this.cache.get(name);
+        }
+        this.cache.set(name, inst);
+        return inst;
+    }
+};
+import { atom } from "jotai";
+// @jotai-ignore
+export const scratchAtom = atom(0);
+/* @jotai-label "Cart total" */ export const cartTotalAtom = globalThis.jotaiAtomCache.get("atoms.ts/cartTotalAtom", atom(0));
+cartTotalAtom.debugLabel = "Cart total";
+/**
+ * Items in the cart.
+ * @jotai-label 'Cart items'
+ */ export const cartItemsAtom = globalThis.jotaiAtomCache.get("atoms.ts/cartItemsAtom", atom([]));
+cartItemsAtom.debugLabel = "Cart items";
+export const countAtom = globalThis.jotaiAtomCache.get("atoms.ts/countAtom", atom(0));
+countAtom.debugLabel = "countAtom";
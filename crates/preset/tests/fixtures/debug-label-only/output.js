@@ -0,0 +1,23 @@
+import { atom } from "jotai";
+import * as utils from "jotai/utils";
+export const countAtom = atom(0);
+countAtom.debugLabel = "countAtom";
+const doubleAtom = atom((get)=>get(countAtom) * 2);
+doubleAtom.debugLabel = "doubleAtom";
+const resettableAtom = utils.atomWithReset(0);
+resettableAtom.debugLabel = "resettableAtom";
+const atoms = {
+    text: atom(""),
+    list: [
+        atom(1),
+        atom(2)
+    ]
+};
+atoms.text.debugLabel = "atoms.text";
+atoms.list[0].debugLabel = "atoms.list.0";
+atoms.list[1].debugLabel = "atoms.list.1";
+function Counter() {
+    const localAtom = atom(0);
+    localAtom.debugLabel = "localAtom";
+    return localAtom;
+}
@@ -0,0 +1,5 @@
+import { atom } from "jotai";
+
+const countAtom = atom(0);
+
+export default atom((get) => get(countAtom) + 1);
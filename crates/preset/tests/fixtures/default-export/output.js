@@ -0,0 +1,16 @@
+globalThis.jotaiAtomCache = globalThis.jotaiAtomCache || {
+    cache: new Map(),
+    get (name, inst) {
+        if (this.cache.has(name)) {
+            return this.cache.get(name);
+        }
+        this.cache.set(name, inst);
+        return inst;
+    }
+};
+import { atom } from "jotai";
+const countAtom = globalThis.jotaiAtomCache.get("atoms.ts/countAtom", atom(0));
+countAtom.debugLabel = "countAtom";
+const atoms = globalThis.jotaiAtomCache.get("atoms.ts/atoms", atom((get)=>get(countAtom) + 1));
+atoms.debugLabel = "atoms";
+export default atoms;
@@ -0,0 +1,4 @@
+"use client";
+import { atom } from "jotai";
+
+export const countAtom = atom(0);
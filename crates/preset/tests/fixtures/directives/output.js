@@ -0,0 +1,14 @@
+"use client";
+globalThis.jotaiAtomCache = globalThis.jotaiAtomCache || {
+    cache: new Map(),
+    get (name, inst) {
+        if (this.cache.has(name)) {
+            return this.cache.get(name);
+        }
+        this.cache.set(name, inst);
+        return inst;
+    }
+};
+import { atom } from "jotai";
+export const countAtom = globalThis.jotaiAtomCache.get("atoms.ts/countAtom", atom(0));
+countAtom.debugLabel = "countAtom";
@@ -0,0 +1,6 @@
+import { atom } from "jotai";
+
+export const countAtom = atom(0);
+export const atoms = {
+  text: atom(""),
+};
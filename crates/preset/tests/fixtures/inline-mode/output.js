@@ -0,0 +1,19 @@
+globalThis.jotaiAtomCache = globalThis.jotaiAtomCache || {
+    cache: new Map(),
+    get (name, inst) {
+        if (this.cache.has(name)) {
+            return this.cache.get(name);
+        }
+        this.cache.set(name, inst);
+        return inst;
+    }
+};
+import { atom } from "jotai";
+export const countAtom = /*#__PURE__*/ Object.assign(globalThis.jotaiAtomCache.get("atoms.ts/countAtom", atom(0)), {
+    debugLabel: "countAtom"
+});
+export const atoms = {
+    text: /*#__PURE__*/ Object.assign(globalThis.jotaiAtomCache.get("atoms.ts/atoms.text", atom("")), {
+        debugLabel: "atoms.text"
+    })
+};
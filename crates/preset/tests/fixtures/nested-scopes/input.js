@@ -0,0 +1,12 @@
+import { atom } from "jotai";
+
+export function useScopedAtom() {
+  const scopedAtom = atom(0);
+  if (scopedAtom) {
+    const innerAtom = atom(1);
+    return innerAtom;
+  }
+  return scopedAtom;
+}
+
+const topAtom = atom(2);
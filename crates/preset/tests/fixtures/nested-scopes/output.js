@@ -0,0 +1,23 @@
+globalThis.jotaiAtomCache = globalThis.jotaiAtomCache || {
+    cache: new Map(),
+    get (name, inst) {
+        if (this.cache.has(name)) {
+            return this.cache.get(name);
+        }
+        this.cache.set(name, inst);
+        return inst;
+    }
+};
+import { atom } from "jotai";
+export function useScopedAtom() {
+    const scopedAtom = atom(0);
+    scopedAtom.debugLabel = "scopedAtom";
+    if (scopedAtom) {
+        const innerAtom = atom(1);
+        innerAtom.debugLabel = "innerAtom";
+        return innerAtom;
+    }
+    return scopedAtom;
+}
+const topAtom = globalThis.jotaiAtomCache.get("atoms.ts/topAtom", atom(2));
+topAtom.debugLabel = "topAtom";
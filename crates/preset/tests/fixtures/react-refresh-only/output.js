@@ -0,0 +1,26 @@
+globalThis.jotaiAtomCache = globalThis.jotaiAtomCache || {
+    cache: new Map(),
+    get (name, inst) {
+        if (this.cache.has(name)) {
+            return this.cache.get(name);
+        }
+        this.cache.set(name, inst);
+        return inst;
+    }
+};
+import { atom } from "jotai";
+import * as utils from "jotai/utils";
+export const countAtom = globalThis.jotaiAtomCache.get("atoms.ts/countAtom", atom(0));
+const doubleAtom = globalThis.jotaiAtomCache.get("atoms.ts/doubleAtom", atom((get)=>get(countAtom) * 2));
+const resettableAtom = globalThis.jotaiAtomCache.get("atoms.ts/resettableAtom", utils.atomWithReset(0));
+const atoms = {
+    text: globalThis.jotaiAtomCache.get("atoms.ts/atoms.text", atom("")),
+    list: [
+        globalThis.jotaiAtomCache.get("atoms.ts/atoms.list.0", atom(1)),
+        globalThis.jotaiAtomCache.get("atoms.ts/atoms.list.1", atom(2))
+    ]
+};
+function Counter() {
+    const localAtom = atom(0);
+    return localAtom;
+}
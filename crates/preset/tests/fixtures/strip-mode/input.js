@@ -0,0 +1,6 @@
+import { atom } from "jotai";
+
+export const countAtom = atom(0);
+countAtom.debugLabel = "count";
+
+export const textAtom = Object.assign(atom(""), { debugLabel: "text" });
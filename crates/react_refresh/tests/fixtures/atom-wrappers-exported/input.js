@@ -0,0 +1,12 @@
+import { atom } from "jotai";
+
+export function createAtom(init) {
+  return atom(init);
+}
+
+const createText = (init) => atom(String(init));
+
+export { createText };
+
+const countAtom = createAtom(0);
+const textAtom = createText("");
@@ -0,0 +1,18 @@
+globalThis.jotaiAtomCache = globalThis.jotaiAtomCache || {
+    cache: new Map(),
+    get (name, inst) {
+        if (this.cache.has(name)) {
+            return this.cache.get(name);
+        }
+        this.cache.set(name, inst);
+        return inst;
+    }
+};
+import { atom } from "jotai";
+export function createAtom(init) {
+    return atom(init);
+}
+const createText = (init)=>atom(String(init));
+export { createText };
+const countAtom = globalThis.jotaiAtomCache.get("atoms.ts/countAtom", createAtom(0));
+const textAtom = globalThis.jotaiAtomCache.get("atoms.ts/textAtom", createText(""));
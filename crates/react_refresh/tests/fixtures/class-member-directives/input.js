@@ -0,0 +1,13 @@
+import { atom } from "jotai";
+
+class CartStore {
+  // @jotai-ignore
+  static items = atom([]);
+  static filters = atom("");
+
+  static {
+    // @jotai-ignore
+    this.countAtom = atom(0);
+    this.totalAtom = atom(0);
+  }
+}
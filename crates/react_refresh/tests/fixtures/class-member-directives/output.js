@@ -0,0 +1,21 @@
+globalThis.jotaiAtomCache = globalThis.jotaiAtomCache || {
+    cache: new Map(),
+    get (name, inst) {
+        if (this.cache.has(name)) {
+            return this.cache.get(name);
+        }
+        this.cache.set(name, inst);
+        return inst;
+    }
+};
+import { atom } from "jotai";
+class CartStore {
+    // @jotai-ignore
+    static items = atom([]);
+    static filters = globalThis.jotaiAtomCache.get("atoms.ts/CartStore.filters", atom(""));
+    static{
+        // @jotai-ignore
+        this.countAtom = atom(0);
+        this.totalAtom = globalThis.jotaiAtomCache.get("atoms.ts/CartStore.totalAtom", atom(0));
+    }
+}
@@ -0,0 +1,36 @@
+import { atom } from "jotai";
+
+class CartStore {
+  static items = atom([]);
+  static filters = { search: atom("") };
+  static #secret = atom(0);
+  totalAtom = atom((get) => get(CartStore.items).length);
+
+  static {
+    this.countAtom = atom(0);
+    const localAtom = atom(0);
+  }
+
+  constructor() {
+    this.selectedAtom = atom(null);
+    const draftAtom = atom("");
+  }
+
+  createAtom() {
+    return atom(0);
+  }
+}
+
+export const UserStore = class {
+  static user = atom(null);
+};
+
+export default class SessionStore {
+  static session = atom(null);
+}
+
+function createStore() {
+  return class {
+    static items = atom([]);
+  };
+}
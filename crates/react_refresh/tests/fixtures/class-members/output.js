@@ -0,0 +1,41 @@
+globalThis.jotaiAtomCache = globalThis.jotaiAtomCache || {
+    cache: new Map(),
+    get (name, inst) {
+        if (this.cache.has(name)) {
+            return this.cache.get(name);
+        }
+        this.cache.set(name, inst);
+        return inst;
+    }
+};
+import { atom } from "jotai";
+class CartStore {
+    static items = globalThis.jotaiAtomCache.get("atoms.ts/CartStore.items", atom([]));
+    static filters = {
+        search: globalThis.jotaiAtomCache.get("atoms.ts/CartStore.filters.search", atom(""))
+    };
+    static #secret = atom(0);
+    totalAtom = atom((get)=>get(CartStore.items).length);
+    static{
+        this.countAtom = globalThis.jotaiAtomCache.get("atoms.ts/CartStore.countAtom", atom(0));
+        const localAtom = atom(0);
+    }
+    constructor(){
+        this.selectedAtom = atom(null);
+        const draftAtom = atom("");
+    }
+    createAtom() {
+        return atom(0);
+    }
+}
+export const UserStore = class {
+    static user = globalThis.jotaiAtomCache.get("atoms.ts/UserStore.user", atom(null));
+};
+export default class SessionStore {
+    static session = globalThis.jotaiAtomCache.get("atoms.ts/SessionStore.session", atom(null));
+}
+function createStore() {
+    return class {
+        static items = atom([]);
+    };
+}
@@ -0,0 +1,15 @@
+import { atom } from "jotai";
+
+// @jotai-ignore
+export const scratchAtom = atom(0);
+
+/* @jotai-label "Cart total" */
+export const cartTotalAtom = atom(0);
+
+/**
+ * Items in the cart.
+ * @jotai-label 'Cart items'
+ */
+export const cartItemsAtom = atom([]);
+
+export const countAtom = atom(0);
@@ -0,0 +1,5 @@
+import { createAtom } from "@/lib/state";
+
+const myCustomAtom = customAtom(0);
+const countAtom = createAtom(0);
+const textAtom = createText("");
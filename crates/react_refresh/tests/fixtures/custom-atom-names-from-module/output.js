@@ -0,0 +1,14 @@
+globalThis.jotaiAtomCache = globalThis.jotaiAtomCache || {
+    cache: new Map(),
+    get (name, inst) {
+        if (this.cache.has(name)) {
+            return this.cache.get(name);
+        }
+        this.cache.set(name, inst);
+        return inst;
+    }
+};
+import { createAtom } from "@/lib/state";
+const myCustomAtom = globalThis.jotaiAtomCache.get("atoms.ts/myCustomAtom", customAtom(0));
+const countAtom = globalThis.jotaiAtomCache.get("atoms.ts/countAtom", createAtom(0));
+const textAtom = createText("");
@@ -0,0 +1,4 @@
+/** @jotai-disable */
+import { atom } from "jotai";
+
+export const countAtom = atom(0);
@@ -0,0 +1,3 @@
+const myCustomAtom = customAtom(0);
+const myLocalAtom = localAtom(0);
+const myEditorAtom = editorAtom(0);
@@ -0,0 +1,21 @@
+import { atom } from "jotai";
+
+const countAtom = atom(0);
+
+function createAtom(atom) {
+  const valueAtom = atom(0);
+  return valueAtom;
+}
+
+try {
+  load();
+} catch (atom) {
+  const errorAtom = atom(1);
+}
+
+{
+  const atom = (value) => ({ value });
+  const boxAtom = atom(2);
+}
+
+const textAtom = atom("");
@@ -0,0 +1,27 @@
+globalThis.jotaiAtomCache = globalThis.jotaiAtomCache || {
+    cache: new Map(),
+    get (name, inst) {
+        if (this.cache.has(name)) {
+            return this.cache.get(name);
+        }
+        this.cache.set(name, inst);
+        return inst;
+    }
+};
+import { atom } from "jotai";
+const countAtom = globalThis.jotaiAtomCache.get("atoms.ts/countAtom", atom(0));
+function createAtom(atom) {
+    const valueAtom = atom(0);
+    return valueAtom;
+}
+try {
+    load();
+} catch (atom) {
+    const errorAtom = atom(1);
+}
+{
+    const atom = (value)=>({
+            value
+        });
+    const boxAtom = atom(2);
+}const textAtom = globalThis.jotaiAtomCache.get("atoms.ts/textAtom", atom(""));
@@ -0,0 +1,31 @@
+globalThis.jotaiAtomCache = globalThis.jotaiAtomCache || {
+    cache: new Map(),
+    get (name, inst) {
+        if (this.cache.has(name)) {
+            return this.cache.get(name);
+        }
+        this.cache.set(name, inst);
+        return inst;
+    }
+};
+import { atom, type Atom, type PrimitiveAtom } from "jotai";
+export const asAtom = globalThis.jotaiAtomCache.get("atoms.ts/asAtom", atom(0)) as PrimitiveAtom<number>;
+export const satisfiesAtom = globalThis.jotaiAtomCache.get("atoms.ts/satisfiesAtom", atom(0)) satisfies Atom<number>;
+export const nonNullAtom = globalThis.jotaiAtomCache.get("atoms.ts/nonNullAtom", atom<number>(0))!;
+export const assertionAtom = <PrimitiveAtom<number>>globalThis.jotaiAtomCache.get("atoms.ts/assertionAtom", atom(0));
+export const parenAtom = globalThis.jotaiAtomCache.get("atoms.ts/parenAtom", atom(0));
+export const instantiationAtom = globalThis.jotaiAtomCache.get("atoms.ts/instantiationAtom", atom<string>(""));
+export const atoms = {
+    count: globalThis.jotaiAtomCache.get("atoms.ts/atoms.count", atom(0)) as PrimitiveAtom<number>,
+    list: [
+        globalThis.jotaiAtomCache.get("atoms.ts/atoms.list.0", atom("")) satisfies Atom<string>
+    ]
+} as const;
+function boxed<T>(value: T) {
+    return atom({
+        value
+    }) as PrimitiveAtom<{
+        value: T;
+    }>;
+}
+export const boxAtom = globalThis.jotaiAtomCache.get("atoms.ts/boxAtom", boxed(1));